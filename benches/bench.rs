@@ -44,7 +44,7 @@ fn smsig() {
         "signing time {}",
         start.elapsed().as_nanos() / NUM_REPETITIONS as u128
     );
-    let sig = SMSigScheme::sign(&sk, 0, message.as_ref(), &pp);
+    let sig = SMSigScheme::sign(&sk, 0, message.as_ref(), &pp).unwrap();
 
     // ===============================
     // verify
@@ -69,13 +69,13 @@ fn smsig() {
     // ===============================
     let start = Instant::now();
     for _ in 0..NUM_REPETITIONS {
-        SMSigScheme::aggregate(&sigs, &pks);
+        SMSigScheme::aggregate(&sigs, &pks, message.as_ref()).unwrap();
     }
     println!(
         "aggregating time {}",
         start.elapsed().as_nanos() / NUM_REPETITIONS as u128
     );
-    let agg_sig = SMSigScheme::aggregate(&sigs, &pks);
+    let agg_sig = SMSigScheme::aggregate(&sigs, &pks, message.as_ref()).unwrap();
     // ===============================
     // batch verification
     // ===============================
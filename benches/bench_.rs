@@ -1,3 +1,9 @@
+#![allow(
+    clippy::needless_range_loop,
+    clippy::clone_on_copy,
+    clippy::useless_format
+)]
+
 #[macro_use]
 extern crate criterion;
 
@@ -5,9 +11,9 @@ use criterion::Criterion;
 use rand::{RngCore, SeedableRng};
 use rand_chacha::ChaCha20Rng;
 use sync_multi_sig::{
-    HOTSHash, HVCHash, LargeNTTPoly, LargePoly, MultiSig, Path, RandomizedPath, Randomizers,
-    SMSigScheme, SignedPoly, SmallNTTPoly, SmallPoly, TerPolyCoeffEncoding, Tree, ALPHA, HEIGHT,
-    SMALL_MODULUS_BITS,
+    AggregateItem, HOTSHash, HVCHash, HashMode, LargeNTTPoly, LargePoly, MultiSig, Path,
+    RandomizedPath, Randomizers, SMSigParam, SMSigScheme, SMSignature, SignedPoly, SmallNTTPoly,
+    SmallPoly, TerPolyCoeffEncoding, Tree, ALPHA, HEIGHT, SMALL_MODULUS_BITS,
 };
 
 criterion_main!(bench);
@@ -15,6 +21,7 @@ criterion_group!(
     bench,
     // bench_smsig,
     // bench_smsig_agg,
+    bench_smsig_slots,
     bench_hash,
     bench_ter_poly,
     bench_hvc_ntt,
@@ -179,6 +186,8 @@ fn bench_hash(c: &mut Criterion) {
         .map(|_| (0..46).map(|_| SignedPoly::rand_binary(&mut rng)).collect())
         .collect();
     let bench_str = format!("{} hots_hash digests", num_tests);
+    let hots_hasher = hasher.clone();
+    let hots_inputs = inputs.clone();
     bench_group.bench_function(bench_str, move |b| {
         b.iter(|| {
             for i in 0..num_tests {
@@ -186,6 +195,42 @@ fn bench_hash(c: &mut Criterion) {
             }
         });
     });
+
+    // the tree hashes binary decompositions of the nodes
+    let hvc_hasher = HVCHash::init(&mut rng);
+    let hvc_inputs: Vec<Vec<SmallPoly>> = (0..num_tests)
+        .map(|_| {
+            (0..28)
+                .map(|_| (&SignedPoly::rand_binary(&mut rng)).into())
+                .collect()
+        })
+        .collect();
+    for mode in [HashMode::Ntt, HashMode::FixedBase] {
+        let hasher = hvc_hasher.clone().with_mode(mode);
+        let inputs = hvc_inputs.clone();
+        let bench_str = format!(
+            "{} hvc_hash digests of binary inputs, {:?}",
+            num_tests, mode
+        );
+        bench_group.bench_function(bench_str, move |b| {
+            b.iter(|| {
+                for i in 0..num_tests {
+                    let _ = hasher.hash(&inputs[i]);
+                }
+            });
+        });
+
+        let hasher = hots_hasher.clone().with_mode(mode);
+        let inputs = hots_inputs.clone();
+        let bench_str = format!("{} hots_hash digests, {:?}", num_tests, mode);
+        bench_group.bench_function(bench_str, move |b| {
+            b.iter(|| {
+                for i in 0..num_tests {
+                    let _ = hasher.hash(&inputs[i]);
+                }
+            });
+        });
+    }
 }
 
 fn bench_decompose(c: &mut Criterion) {
@@ -233,10 +278,10 @@ fn bench_tree(c: &mut Criterion) {
         .collect();
     let trees: Vec<Tree> = leaves
         .iter()
-        .map(|x| Tree::new_with_leaf_nodes(x, &hasher))
+        .map(|x| Tree::new_with_leaf_nodes(x, &hasher).unwrap())
         .collect();
 
-    let proofs: Vec<Path> = trees.iter().map(|x| x.gen_proof(0)).collect();
+    let proofs: Vec<Path> = trees.iter().map(|x| x.gen_proof(0).unwrap()).collect();
 
     let hasher_clone = hasher.clone();
     let mut bench_group = c.benchmark_group("tree");
@@ -288,7 +333,7 @@ fn bench_randomization(c: &mut Criterion) {
         .map(|_| SignedPoly::rand_ternary(&mut rng, ALPHA >> 1))
         .collect();
 
-    let agg_path = Path::aggregation(&proofs, &roots);
+    let agg_path = Path::aggregation(&proofs, &roots).unwrap();
 
     let mut bench_group = c.benchmark_group("randomization");
     bench_group.sample_size(10);
@@ -308,7 +353,9 @@ fn bench_randomization(c: &mut Criterion) {
     bench_group.bench_function(bench_str, move |b| {
         b.iter(|| {
             for i in 0..num_tests {
-                decomposed_proofs[i].randomize_with(&randomizers[i]);
+                decomposed_proofs[i]
+                    .randomize_with(&randomizers[i])
+                    .unwrap();
                 decomposed_proofs[i] = decomposed_proofs_clone[i].clone();
             }
         });
@@ -362,7 +409,7 @@ fn bench_smsig(c: &mut Criterion) {
     let bench_str = format!("Sign");
     let pp_clone = pp.clone();
 
-    let sig = SMSigScheme::sign(&sk, 0, message.as_ref(), &pp);
+    let sig = SMSigScheme::sign(&sk, 0, message.as_ref(), &pp).unwrap();
     bench_group.bench_function(bench_str, move |b| {
         b.iter(|| SMSigScheme::sign(&sk, 0, message.as_ref(), &pp_clone));
     });
@@ -391,7 +438,7 @@ fn bench_smsig_agg(c: &mut Criterion) {
         rng.fill_bytes(&mut seed);
         let (pk, sk) = SMSigScheme::key_gen(&seed, &pp);
 
-        let sig = SMSigScheme::sign(&sk, 0, message.as_ref(), &pp);
+        let sig = SMSigScheme::sign(&sk, 0, message.as_ref(), &pp).unwrap();
         assert!(SMSigScheme::verify(&pk, message.as_ref(), &sig, &pp));
         pks.push(pk);
         sigs.push(sig);
@@ -400,10 +447,10 @@ fn bench_smsig_agg(c: &mut Criterion) {
     // aggregation
     // ===============================
     let bench_str = format!("aggregation");
-    let agg_sig = SMSigScheme::aggregate(&sigs, &pks);
+    let agg_sig = SMSigScheme::aggregate(&sigs, &pks, message.as_ref()).unwrap();
     let pks_clone = pks.clone();
     bench_group.bench_function(bench_str, move |b| {
-        b.iter(|| SMSigScheme::aggregate(&sigs, &pks_clone));
+        b.iter(|| SMSigScheme::aggregate(&sigs, &pks_clone, message.as_ref()));
     });
     // ===============================
     // batch verification
@@ -420,3 +467,63 @@ fn bench_smsig_agg(c: &mut Criterion) {
         });
     });
 }
+
+fn bench_smsig_slots(c: &mut Criterion) {
+    let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+    let height = 10;
+    let num_slots = 32;
+    let pp = SMSigParam::setup_with_height(&mut rng, height).unwrap();
+
+    let mut bench_group = c.benchmark_group("smsig slots");
+    bench_group.sample_size(10);
+
+    let keys: Vec<_> = (0..16u8)
+        .map(|i| SMSigScheme::key_gen(&[i; 32], &pp))
+        .collect();
+    let pks: Vec<_> = keys.iter().map(|(pk, _)| *pk).collect();
+    let messages: Vec<String> = (0..num_slots).map(|i| format!("slot {}", i)).collect();
+    let agg_sigs: Vec<SMSignature> = messages
+        .iter()
+        .enumerate()
+        .map(|(index, message)| {
+            let sigs: Vec<_> = keys
+                .iter()
+                .map(|(_, sk)| SMSigScheme::sign(sk, index, message.as_ref(), &pp).unwrap())
+                .collect();
+            SMSigScheme::aggregate(&sigs, &pks, message.as_ref()).unwrap()
+        })
+        .collect();
+
+    // ===============================
+    // one slot at a time
+    // ===============================
+    let bench_str = format!("batch verification of {} slots", num_slots);
+    bench_group.bench_function(bench_str, |b| {
+        b.iter(|| {
+            for (message, agg_sig) in messages.iter().zip(agg_sigs.iter()) {
+                assert!(SMSigScheme::batch_verify(
+                    &pks,
+                    message.as_ref(),
+                    agg_sig,
+                    &pp
+                ))
+            }
+        });
+    });
+    // ===============================
+    // all slots at once
+    // ===============================
+    let items: Vec<AggregateItem> = messages
+        .iter()
+        .zip(agg_sigs.iter())
+        .map(|(message, signature)| AggregateItem {
+            committee: &pks,
+            message: message.as_ref(),
+            signature,
+        })
+        .collect();
+    let bench_str = format!("verify aggregates of {} slots", num_slots);
+    bench_group.bench_function(bench_str, |b| {
+        b.iter(|| assert!(SMSigScheme::verify_aggregates(&items, &pp, &mut rng).is_empty()));
+    });
+}
@@ -1,15 +1,27 @@
 fn main() {
     pkg_config::Config::new();
-    let src = ["c/poly.c", "c/hvc_ntt.c", "c/hots_ntt.c"];
+    let src = ["c/hvc_ntt.c", "c/hots_ntt.c"];
 
     let mut builder = cc::Build::new();
     let build = builder
         .files(src.iter())
         .include("c")
         .flag("-Wno-unused-parameter")
-        .flag("-mavx2")
         .flag("-o3")
         .define("USE_ZLIB", None);
 
+    // `poly.c` is written with AVX2 intrinsics; it is only built for x86_64,
+    // and only called after checking that the CPU supports AVX2
+    if std::env::var("CARGO_CFG_TARGET_ARCH").as_deref() == Ok("x86_64") {
+        cc::Build::new()
+            .file("c/poly.c")
+            .include("c")
+            .flag("-Wno-unused-parameter")
+            .flag("-mavx2")
+            .flag("-o3")
+            .define("USE_ZLIB", None)
+            .compile("cpoly_avx2");
+    }
+
     build.compile("cpoly");
 }
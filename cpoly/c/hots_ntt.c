@@ -279,6 +279,18 @@ static const uint32_t HOTS_INV_NTT_TABLE[] = {
     5229082, 6213301, 3850593, 3036480, 3876295, 5495390, 1052492, 946,
 };
 
+#define HOTS_Q 0x662801
+
+/// x % HOTS_Q with a Barrett reduction; the NTT runs on secret keys, and
+/// unlike a division, this takes the same time for every x
+static inline uint32_t hots_rem(uint64_t x) {
+  // floor(2^64 / HOTS_Q), so the quotient is too small by at most one
+  const uint64_t m = UINT64_MAX / HOTS_Q;
+  uint64_t r = x - (uint64_t)(((unsigned __int128)x * m) >> 64) * HOTS_Q;
+  uint64_t y = r - HOTS_Q;
+  return (uint32_t)(y + (HOTS_Q & (uint64_t)((int64_t)y >> 63)));
+}
+
 /// convert a polynomial into its NTT form
 void hots_ntt(uint32_t *p) {
   unsigned int t, ht, i, j, j1, j2, l, m;
@@ -296,9 +308,9 @@ void hots_ntt(uint32_t *p) {
       j = j1;
       while (j < j2) {
         u = p[j];
-        v = (uint32_t)(((uint64_t)p[j + ht]) * ((uint64_t)s) % 0x662801);
-        p[j] = ((u + v) % 0x662801);
-        p[j + ht] = (u + 0x662801 - v) % 0x662801;
+        v = hots_rem(((uint64_t)p[j + ht]) * ((uint64_t)s));
+        p[j] = hots_rem(u + v);
+        p[j + ht] = hots_rem(u + HOTS_Q - v);
         j++;
       }
       i++;
@@ -328,9 +340,8 @@ void hots_inv_ntt(uint32_t *p) {
       while (j < j2) {
         u = p[j];
         v = p[j + t];
-        p[j] = (u + v) % 0x662801;
-        p[j + t] = (uint32_t)(((uint64_t)(u + 0x662801 - v)) * ((uint64_t)s) %
-                              0x662801);
+        p[j] = hots_rem(u + v);
+        p[j + t] = hots_rem(((uint64_t)(u + HOTS_Q - v)) * ((uint64_t)s));
         j++;
       }
       i++;
@@ -341,6 +352,6 @@ void hots_inv_ntt(uint32_t *p) {
   }
 
   for (i = 0; i < N; i++) {
-    p[i] = (uint32_t)(((uint64_t)p[i]) * 6681837 % 0x662801);
+    p[i] = hots_rem(((uint64_t)p[i]) * 6681837);
   }
 }
@@ -1,9 +1,84 @@
-#![allow(non_upper_case_globals)]
-#![allow(non_camel_case_types)]
-#![allow(non_snake_case)]
-#![allow(deref_nullptr)]
-#![allow(dead_code)]
-#![allow(improper_ctypes)]
+//! Polynomial arithmetic for the Squirrel rings.
+//!
+//! The NTTs and the ternary multiplication are backed either by the C sources
+//! in `c/` or by the pure-Rust implementations in [`portable`], which produce
+//! bit-for-bit identical results. The C `ternary_mul` requires AVX2 and is only
+//! used on x86_64 CPUs that support it; the `portable` feature selects the Rust
+//! implementations throughout.
 
+pub mod ffi {
+    //! Raw bindings to the C sources, generated by `make build`.
+    #![allow(non_upper_case_globals)]
+    #![allow(dead_code)]
 
-include!("./bindings.rs");
\ No newline at end of file
+    include!("./bindings.rs");
+}
+
+pub mod portable;
+mod tables;
+
+/// The degree of both rings.
+pub const N: usize = ffi::N as usize;
+
+/// The number of non-zero coefficients of a ternary polynomial; the first half
+/// of the indices are the +1 positions, the second half the -1 positions.
+pub const TERNARY_WEIGHT: usize = 20;
+
+/// The twiddle factors of [`hvc_ntt`]: block `i` of layer `l` uses
+/// `HVC_NTT_TABLE[(1 << l) + i]`.
+pub const HVC_NTT_TABLE: [u16; N] = tables::NTT_TABLE;
+
+/// Convert a polynomial modulo 12289 into its NTT form.
+pub fn hvc_ntt(p: &mut [u16; N]) {
+    if cfg!(feature = "portable") {
+        portable::hvc_ntt(p)
+    } else {
+        unsafe { ffi::hvc_ntt(p.as_mut_ptr()) }
+    }
+}
+
+/// Convert an NTT form polynomial modulo 12289 into its integer form.
+pub fn hvc_inv_ntt(p: &mut [u16; N]) {
+    if cfg!(feature = "portable") {
+        portable::hvc_inv_ntt(p)
+    } else {
+        unsafe { ffi::hvc_inv_ntt(p.as_mut_ptr()) }
+    }
+}
+
+/// Convert a polynomial modulo 0x662801 into its NTT form.
+pub fn hots_ntt(p: &mut [u32; N]) {
+    if cfg!(feature = "portable") {
+        portable::hots_ntt(p)
+    } else {
+        unsafe { ffi::hots_ntt(p.as_mut_ptr()) }
+    }
+}
+
+/// Convert an NTT form polynomial modulo 0x662801 into its integer form.
+pub fn hots_inv_ntt(p: &mut [u32; N]) {
+    if cfg!(feature = "portable") {
+        portable::hots_inv_ntt(p)
+    } else {
+        unsafe { ffi::hots_inv_ntt(p.as_mut_ptr()) }
+    }
+}
+
+/// Compute `a * b mod x^N + 1` with wrapping `i8` arithmetic, where `b` is a
+/// ternary polynomial given by its non-zero indices.
+pub fn ternary_mul(res: &mut [i8; N], a: &[i8; N], b_indices: &[u8; TERNARY_WEIGHT]) {
+    #[cfg(all(target_arch = "x86_64", not(feature = "portable")))]
+    if std::is_x86_feature_detected!("avx2") {
+        let mut buf = [0u8; 2 * N];
+        unsafe {
+            ffi::ternary_mul(
+                res.as_mut_ptr(),
+                buf.as_mut_ptr(),
+                a.as_ptr(),
+                b_indices.as_ptr(),
+            );
+        }
+        return;
+    }
+    portable::ternary_mul(res, a, b_indices)
+}
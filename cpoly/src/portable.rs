@@ -0,0 +1,284 @@
+//! Pure-Rust versions of the C routines, for targets without AVX2 or without
+//! a C toolchain. They follow the C code step by step, including its integer
+//! promotions and wrapping, so that they agree on every input.
+
+use crate::{
+    tables::{HOTS_INV_NTT_TABLE, HOTS_NTT_TABLE, INV_NTT_TABLE, NTT_TABLE},
+    N, TERNARY_WEIGHT,
+};
+
+const HVC_Q: u32 = 12289;
+// N^{-1} mod HVC_Q
+const HVC_N_INV: u32 = 12265;
+const HOTS_Q: u32 = 0x662801;
+// N^{-1} mod HOTS_Q
+const HOTS_N_INV: u64 = 6681837;
+
+/// Convert a polynomial into its NTT form; see `hvc_ntt` in `c/hvc_ntt.c`.
+pub fn hvc_ntt(p: &mut [u16; N]) {
+    let mut t = N;
+    for l in 0..9 {
+        let m = 1 << l;
+        let ht = t >> 1;
+        for i in 0..m {
+            let s = NTT_TABLE[m + i] as u32;
+            let j1 = i * t;
+            for j in j1..j1 + ht {
+                let u = p[j] as u32;
+                let v = p[j + ht] as u32 * s % HVC_Q;
+                p[j] = ((u + v) % HVC_Q) as u16;
+                p[j + ht] = ((u + HVC_Q - v) % HVC_Q) as u16;
+            }
+        }
+        t = ht;
+    }
+}
+
+/// Convert an NTT form polynomial into its integer form; see `hvc_inv_ntt` in
+/// `c/hvc_ntt.c`.
+pub fn hvc_inv_ntt(p: &mut [u16; N]) {
+    let mut t = 1;
+    let mut m = N;
+    while m > 1 {
+        let hm = m >> 1;
+        let dt = t << 1;
+        for i in 0..hm {
+            let s = INV_NTT_TABLE[hm + i] as u32;
+            let j1 = i * dt;
+            for j in j1..j1 + t {
+                // the C code computes in `int`, so the difference may be
+                // negative for unreduced inputs
+                let u = p[j] as i32;
+                let v = p[j + t] as i32;
+                p[j] = ((u + v) % HVC_Q as i32) as u16;
+                p[j + t] = (((u + HVC_Q as i32 - v) as u32).wrapping_mul(s) % HVC_Q) as u16;
+            }
+        }
+        t = dt;
+        m = hm;
+    }
+
+    for e in p.iter_mut() {
+        *e = (*e as u32 * HVC_N_INV % HVC_Q) as u16;
+    }
+}
+
+/// `x % HOTS_Q` with a Barrett reduction, as `hots_rem` in `c/hots_ntt.c`. The
+/// HOTS NTT runs on secret keys, and unlike a division, this takes the same
+/// time for every `x`.
+#[inline(always)]
+fn hots_rem(x: u64) -> u32 {
+    // floor(2^64 / HOTS_Q), so the quotient is too small by at most one
+    const M: u128 = (u64::MAX / HOTS_Q as u64) as u128;
+    let r = x - ((x as u128 * M) >> 64) as u64 * HOTS_Q as u64;
+    let y = r.wrapping_sub(HOTS_Q as u64);
+    y.wrapping_add(HOTS_Q as u64 & ((y as i64 >> 63) as u64)) as u32
+}
+
+/// Convert a polynomial into its NTT form; see `hots_ntt` in `c/hots_ntt.c`.
+pub fn hots_ntt(p: &mut [u32; N]) {
+    let mut t = N;
+    for l in 0..9 {
+        let m = 1 << l;
+        let ht = t >> 1;
+        for i in 0..m {
+            let s = HOTS_NTT_TABLE[m + i] as u64;
+            let j1 = i * t;
+            for j in j1..j1 + ht {
+                let u = p[j];
+                let v = hots_rem(p[j + ht] as u64 * s);
+                p[j] = hots_rem(u.wrapping_add(v) as u64);
+                p[j + ht] = hots_rem(u.wrapping_add(HOTS_Q).wrapping_sub(v) as u64);
+            }
+        }
+        t = ht;
+    }
+}
+
+/// Convert an NTT form polynomial into its integer form; see `hots_inv_ntt` in
+/// `c/hots_ntt.c`.
+pub fn hots_inv_ntt(p: &mut [u32; N]) {
+    let mut t = 1;
+    let mut m = N;
+    while m > 1 {
+        let hm = m >> 1;
+        let dt = t << 1;
+        for i in 0..hm {
+            let s = HOTS_INV_NTT_TABLE[hm + i] as u64;
+            let j1 = i * dt;
+            for j in j1..j1 + t {
+                let u = p[j];
+                let v = p[j + t];
+                p[j] = hots_rem(u.wrapping_add(v) as u64);
+                p[j + t] = hots_rem(u.wrapping_add(HOTS_Q).wrapping_sub(v) as u64 * s);
+            }
+        }
+        t = dt;
+        m = hm;
+    }
+
+    for e in p.iter_mut() {
+        *e = hots_rem(*e as u64 * HOTS_N_INV);
+    }
+}
+
+/// Compute `a * b mod x^N + 1` with wrapping `i8` arithmetic, where the first
+/// half of `b_indices` are the +1 positions of `b` and the second half the -1
+/// positions; see `ternary_mul` in `c/poly.c`.
+pub fn ternary_mul(res: &mut [i8; N], a: &[i8; N], b_indices: &[u8; TERNARY_WEIGHT]) {
+    let (pos, neg) = b_indices.split_at(TERNARY_WEIGHT / 2);
+    let mut buf = [0i8; 2 * N];
+    for (&p, &n) in pos.iter().zip(neg.iter()) {
+        for (b, &e) in buf[p as usize..].iter_mut().zip(a.iter()) {
+            *b = b.wrapping_add(e);
+        }
+        for (b, &e) in buf[n as usize..].iter_mut().zip(a.iter()) {
+            *b = b.wrapping_sub(e);
+        }
+    }
+    for (i, r) in res.iter_mut().enumerate() {
+        *r = buf[i].wrapping_sub(buf[i + N]);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ffi;
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha20Rng;
+
+    const REPEAT: usize = 100;
+
+    // the C routines on the same input, as reference
+    fn c_hvc(mut p: [u16; N], inverse: bool) -> [u16; N] {
+        unsafe {
+            if inverse {
+                ffi::hvc_inv_ntt(p.as_mut_ptr())
+            } else {
+                ffi::hvc_ntt(p.as_mut_ptr())
+            }
+        }
+        p
+    }
+
+    fn c_hots(mut p: [u32; N], inverse: bool) -> [u32; N] {
+        unsafe {
+            if inverse {
+                ffi::hots_inv_ntt(p.as_mut_ptr())
+            } else {
+                ffi::hots_ntt(p.as_mut_ptr())
+            }
+        }
+        p
+    }
+
+    #[test]
+    fn test_hvc_ntt() {
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+        for i in 0..REPEAT {
+            // reduced inputs, and also arbitrary ones for the first half
+            let bound = if i < REPEAT / 2 {
+                u16::MAX
+            } else {
+                HVC_Q as u16 - 1
+            };
+            let mut p = [0u16; N];
+            p.iter_mut().for_each(|e| *e = rng.gen_range(0..=bound));
+
+            let mut q = p;
+            hvc_ntt(&mut q);
+            assert_eq!(q, c_hvc(p, false));
+
+            let mut r = p;
+            hvc_inv_ntt(&mut r);
+            assert_eq!(r, c_hvc(p, true));
+            if bound < HVC_Q as u16 {
+                hvc_inv_ntt(&mut q);
+                assert_eq!(q, p);
+            }
+        }
+    }
+
+    #[test]
+    fn test_hots_ntt() {
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+        for i in 0..REPEAT {
+            let bound = if i < REPEAT / 2 { u32::MAX } else { HOTS_Q - 1 };
+            let mut p = [0u32; N];
+            p.iter_mut().for_each(|e| *e = rng.gen_range(0..=bound));
+
+            let mut q = p;
+            hots_ntt(&mut q);
+            assert_eq!(q, c_hots(p, false));
+
+            let mut r = p;
+            hots_inv_ntt(&mut r);
+            assert_eq!(r, c_hots(p, true));
+            if bound < HOTS_Q {
+                hots_inv_ntt(&mut q);
+                assert_eq!(q, p);
+            }
+        }
+    }
+
+    #[test]
+    fn test_hots_ntt_edge_values() {
+        // the Barrett reductions of both versions at the ends of their ranges
+        let edges = [
+            0,
+            1,
+            HOTS_Q - 1,
+            HOTS_Q,
+            HOTS_Q + 1,
+            2 * HOTS_Q - 1,
+            u32::MAX,
+        ];
+        for &a in &edges {
+            for &b in &edges {
+                let mut p = [a; N];
+                p.iter_mut().skip(1).step_by(2).for_each(|e| *e = b);
+                for inverse in [false, true] {
+                    let mut q = p;
+                    if inverse {
+                        hots_inv_ntt(&mut q);
+                    } else {
+                        hots_ntt(&mut q);
+                    }
+                    assert_eq!(q, c_hots(p, inverse));
+                    assert!(q.iter().all(|&e| e < HOTS_Q));
+                }
+            }
+        }
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    #[test]
+    fn test_ternary_mul() {
+        if !std::is_x86_feature_detected!("avx2") {
+            return;
+        }
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+        for _ in 0..REPEAT {
+            let mut a = [0i8; N];
+            a.iter_mut().for_each(|e| *e = rng.gen());
+            let mut b = [0u8; TERNARY_WEIGHT];
+            b.iter_mut().for_each(|e| *e = rng.gen());
+
+            let mut res = [0i8; N];
+            ternary_mul(&mut res, &a, &b);
+
+            let mut expected = [0i8; N];
+            let mut buf = [0u8; 2 * N];
+            unsafe {
+                ffi::ternary_mul(
+                    expected.as_mut_ptr(),
+                    buf.as_mut_ptr(),
+                    a.as_ptr(),
+                    b.as_ptr(),
+                );
+            }
+            assert_eq!(res, expected);
+        }
+    }
+}
@@ -0,0 +1,185 @@
+//! Twiddle factor tables for the portable NTT: the first `N` entries of the
+//! tables in `c/hvc_ntt.c` and `c/hots_ntt.c`.
+
+use crate::N;
+
+pub(crate) const NTT_TABLE: [u16; N] = [
+    1, 10810, 7143, 4043, 10984, 722, 5736, 8155, 3542, 8785, 9744, 3621, 10643, 1212, 3195, 5860,
+    7468, 2639, 9664, 11340, 11726, 9314, 9283, 9545, 5728, 7698, 5023, 5828, 8961, 6512, 7311,
+    1351, 2319, 11119, 11334, 11499, 9088, 3014, 5086, 10963, 4846, 9542, 9154, 3712, 4805, 8736,
+    11227, 9995, 3091, 12208, 7969, 11289, 9326, 7393, 9238, 2366, 11112, 8034, 10654, 9521, 12149,
+    10436, 7678, 11563, 1260, 4388, 4632, 6534, 2426, 334, 1428, 1696, 2013, 9000, 729, 3241, 2881,
+    3284, 7197, 10200, 8595, 7110, 10530, 8582, 3382, 11934, 9741, 8058, 3637, 3459, 145, 6747,
+    9558, 8357, 7399, 6378, 9447, 480, 1022, 9, 9821, 339, 5791, 544, 10616, 4278, 6958, 7300,
+    8112, 8705, 1381, 9764, 11336, 8541, 827, 5767, 2476, 118, 2197, 7222, 3949, 8993, 4452, 2396,
+    7935, 130, 2837, 6915, 2401, 442, 7188, 11222, 390, 773, 8456, 3778, 354, 4861, 9377, 5698,
+    5012, 9808, 2859, 11244, 1017, 7404, 1632, 7205, 27, 9223, 8526, 10849, 1537, 242, 4714, 8146,
+    9611, 3704, 5019, 11744, 1002, 5011, 5088, 8005, 7313, 10682, 8509, 11414, 9852, 3646, 6022,
+    2987, 9723, 10102, 6250, 9867, 11224, 2143, 11885, 7644, 1168, 5277, 11082, 3248, 493, 8193,
+    6845, 2381, 7952, 11854, 1378, 1912, 2166, 3915, 12176, 7370, 12129, 3149, 12286, 4437, 3636,
+    4938, 5291, 2704, 10863, 7635, 1663, 10512, 3364, 1689, 4057, 9018, 9442, 7875, 2174, 4372,
+    7247, 9984, 4053, 2645, 5195, 9509, 7394, 1484, 9042, 9603, 8311, 9320, 9919, 2865, 5332, 3510,
+    1630, 10163, 5407, 3186, 11136, 9405, 10040, 8241, 9890, 8889, 7098, 9153, 9289, 671, 3016,
+    243, 6730, 420, 10111, 1544, 3985, 4905, 3531, 476, 49, 1263, 5915, 1483, 9789, 10800, 10706,
+    6347, 1512, 350, 10474, 5383, 5369, 10232, 9087, 4493, 9551, 6421, 6554, 2655, 9280, 1693, 174,
+    723, 10314, 8532, 347, 2925, 8974, 11863, 1858, 4754, 3030, 4115, 2361, 10446, 2908, 218, 3434,
+    8760, 3963, 576, 6142, 9842, 1954, 10238, 9407, 10484, 3991, 8320, 9522, 156, 2281, 5876,
+    10258, 5333, 3772, 418, 5908, 11836, 5429, 7515, 7552, 1293, 295, 6099, 5766, 652, 8273, 4077,
+    8527, 9370, 325, 10885, 11143, 11341, 5990, 1159, 8561, 8240, 3329, 4298, 12121, 2692, 5961,
+    7183, 10327, 1594, 6167, 9734, 7105, 11089, 1360, 3956, 6170, 5297, 8210, 11231, 922, 441,
+    1958, 4322, 1112, 2078, 4046, 709, 9139, 1319, 4240, 8719, 6224, 11454, 2459, 683, 3656, 12225,
+    10723, 5782, 9341, 9786, 9166, 10542, 9235, 6803, 7856, 6370, 3834, 7032, 7048, 9369, 8120,
+    9162, 6821, 1010, 8807, 787, 5057, 4698, 4780, 8844, 12097, 1321, 4912, 10240, 677, 6415, 6234,
+    8953, 1323, 9523, 12237, 3174, 1579, 11858, 9784, 5906, 3957, 9450, 151, 10162, 12231, 12048,
+    3532, 11286, 1956, 7280, 11404, 6281, 3477, 6608, 142, 11184, 9445, 3438, 11314, 4212, 9260,
+    6695, 4782, 5886, 8076, 504, 2302, 11684, 11868, 8209, 3602, 6068, 8689, 3263, 6077, 7665,
+    7822, 7500, 6752, 4749, 4449, 6833, 12142, 8500, 6118, 8471, 1190, 9606, 3860, 5445, 7753,
+    11239, 5079, 9027, 2169, 11767, 7965, 4916, 8214, 5315, 11011, 9945, 1973, 6715, 8775, 11248,
+    5925, 11271, 654, 3565, 1702, 1987, 6760, 5206, 3199, 12233, 6136, 6427, 6874, 8646, 4948,
+    6152, 400, 10561, 5339, 5446, 3710, 6093, 468, 8301, 316, 11907, 10256, 8291, 3879, 1922,
+    10930, 6854, 973, 11035,
+];
+
+pub(crate) const INV_NTT_TABLE: [u16; N] = [
+    1, 1479, 8246, 5146, 4134, 6553, 11567, 1305, 6429, 9094, 11077, 1646, 8668, 2545, 3504, 8747,
+    10938, 4978, 5777, 3328, 6461, 7266, 4591, 6561, 2744, 3006, 2975, 563, 949, 2625, 9650, 4821,
+    726, 4611, 1853, 140, 2768, 1635, 4255, 1177, 9923, 3051, 4896, 2963, 1000, 4320, 81, 9198,
+    2294, 1062, 3553, 7484, 8577, 3135, 2747, 7443, 1326, 7203, 9275, 3201, 790, 955, 1170, 9970,
+    5374, 9452, 12159, 4354, 9893, 7837, 3296, 8340, 5067, 10092, 12171, 9813, 6522, 11462, 3748,
+    953, 2525, 10908, 3584, 4177, 4989, 5331, 8011, 1673, 11745, 6498, 11950, 2468, 12280, 11267,
+    11809, 2842, 5911, 4890, 3932, 2731, 5542, 12144, 8830, 8652, 4231, 2548, 355, 8907, 3707,
+    1759, 5179, 3694, 2089, 5092, 9005, 9408, 9048, 11560, 3289, 10276, 10593, 10861, 11955, 9863,
+    5755, 7657, 7901, 11029, 11813, 8758, 7384, 8304, 10745, 2178, 11869, 5559, 12046, 9273, 11618,
+    3000, 3136, 5191, 3400, 2399, 4048, 2249, 2884, 1153, 9103, 6882, 2126, 10659, 8779, 6957,
+    9424, 2370, 2969, 3978, 2686, 3247, 10805, 4895, 2780, 7094, 9644, 8236, 2305, 5042, 7917,
+    10115, 4414, 2847, 3271, 8232, 10600, 8925, 1777, 10626, 4654, 1426, 9585, 6998, 7351, 8653,
+    7852, 3, 9140, 160, 4919, 113, 8374, 10123, 10377, 10911, 435, 4337, 9908, 5444, 4096, 11796,
+    9041, 1207, 7012, 11121, 4645, 404, 10146, 1065, 2422, 6039, 2187, 2566, 9302, 6267, 8643,
+    2437, 875, 3780, 1607, 4976, 4284, 7201, 7278, 11287, 545, 7270, 8585, 2678, 4143, 7575, 12047,
+    10752, 1440, 3763, 3066, 12262, 5084, 10657, 4885, 11272, 1045, 9430, 2481, 7277, 6591, 2912,
+    7428, 11935, 8511, 3833, 11516, 11899, 1067, 5101, 11847, 9888, 1254, 11316, 5435, 1359, 10367,
+    8410, 3998, 2033, 382, 11973, 3988, 11821, 6196, 8579, 6843, 6950, 1728, 11889, 6137, 7341,
+    3643, 5415, 5862, 6153, 56, 9090, 7083, 5529, 10302, 10587, 8724, 11635, 1018, 6364, 1041,
+    3514, 5574, 10316, 2344, 1278, 6974, 4075, 7373, 4324, 522, 10120, 3262, 7210, 1050, 4536,
+    6844, 8429, 2683, 11099, 3818, 6171, 3789, 147, 5456, 7840, 7540, 5537, 4789, 4467, 4624, 6212,
+    9026, 3600, 6221, 8687, 4080, 421, 605, 9987, 11785, 4213, 6403, 7507, 5594, 3029, 8077, 975,
+    8851, 2844, 1105, 12147, 5681, 8812, 6008, 885, 5009, 10333, 1003, 8757, 241, 58, 2127, 12138,
+    2839, 8332, 6383, 2505, 431, 10710, 9115, 52, 2766, 10966, 3336, 6055, 5874, 11612, 2049, 7377,
+    10968, 192, 3445, 7509, 7591, 7232, 11502, 3482, 11279, 5468, 3127, 4169, 2920, 5241, 5257,
+    8455, 5919, 4433, 5486, 3054, 1747, 3123, 2503, 2948, 6507, 1566, 64, 8633, 11606, 9830, 835,
+    6065, 3570, 8049, 10970, 3150, 11580, 8243, 10211, 11177, 7967, 10331, 11848, 11367, 1058,
+    4079, 6992, 6119, 8333, 10929, 1200, 5184, 2555, 6122, 10695, 1962, 5106, 6328, 9597, 168,
+    7991, 8960, 4049, 3728, 11130, 6299, 948, 1146, 1404, 11964, 2919, 3762, 8212, 4016, 11637,
+    6523, 6190, 11994, 10996, 4737, 4774, 6860, 453, 6381, 11871, 8517, 6956, 2031, 6413, 10008,
+    12133, 2767, 3969, 8298, 1805, 2882, 2051, 10335, 2447, 6147, 11713, 8326, 3529, 8855, 12071,
+    9381, 1843, 9928, 8174, 9259, 7535, 10431, 426, 3315, 9364, 11942, 3757, 1975, 11566, 12115,
+    10596, 3009, 9634, 5735, 5868, 2738, 7796, 3202, 2057, 6920, 6906, 1815, 11939, 10777, 5942,
+    1583, 1489, 2500, 10806, 6374, 11026, 12240,
+];
+
+pub(crate) const HOTS_NTT_TABLE: [u32; N] = [
+    1, 3907658, 2638481, 1553977, 5395941, 4115851, 480732, 3912073, 1411215, 6000587, 1065596,
+    3254862, 5357550, 4828077, 590351, 3550809, 657937, 5596373, 3502275, 5326654, 4073164,
+    5554234, 3595025, 4795986, 6554050, 4623093, 3746092, 5708210, 5120546, 1651474, 1693279,
+    5339857, 2481694, 3152500, 3061381, 4576909, 3150236, 1255580, 2918321, 258929, 1079128,
+    5260044, 5954450, 4978729, 3742785, 4265294, 2638065, 2832108, 2753360, 2090883, 4475295,
+    6163550, 5487114, 528477, 3789942, 213275, 2715286, 2971790, 4119266, 2989433, 529798, 1446094,
+    4291916, 370297, 4379382, 5581362, 5174130, 6193671, 6455361, 2141357, 6640905, 5644148,
+    1311092, 3579460, 3004500, 4193811, 256255, 5458293, 4684785, 950895, 797994, 4704955, 2122831,
+    5756626, 1517622, 508789, 2736708, 6324227, 3176806, 3017836, 4924468, 1899348, 1651856,
+    1334124, 2307736, 2773591, 5285776, 6573094, 5259088, 5656191, 123486, 5201313, 828708,
+    4807416, 1431848, 5870842, 4506466, 4523946, 3405013, 2210355, 4367554, 786977, 1571697,
+    2271033, 3305840, 3082526, 3239127, 4711679, 3940676, 5383420, 4484607, 727778, 1295245,
+    173471, 5470469, 5335775, 4902977, 3409942, 2374344, 5924954, 3810322, 2293180, 6146472,
+    2129078, 727525, 4419956, 6335155, 6689715, 3507368, 3052499, 3946463, 3584152, 2504673,
+    5052526, 4548560, 6162301, 1483386, 2375980, 2794657, 2460357, 6116077, 3315701, 549869,
+    1144017, 4482237, 5337910, 2443476, 3703347, 4374129, 5257146, 1480046, 5878610, 4141269,
+    420748, 6378933, 1029750, 3594597, 6046612, 4242889, 1251939, 3747093, 749763, 5659578, 830470,
+    235429, 1240300, 1929252, 3363775, 5104226, 4414152, 2200129, 5514889, 179661, 5082019,
+    2566035, 1758714, 2974195, 2239265, 2109116, 5005721, 3942805, 3010443, 2113849, 1906416,
+    5860633, 274197, 869853, 365218, 792850, 1841029, 2861647, 5371477, 5560154, 5032694, 3828280,
+    6632391, 1172538, 6242151, 5022545, 2978329, 5541597, 3225891, 530369, 3312783, 4509142,
+    3962648, 6533814, 2873248, 3375751, 5435999, 194587, 4703271, 1309116, 1281941, 95709, 114603,
+    954582, 2092398, 1223262, 1997352, 2936852, 890319, 2801573, 459304, 3517835, 5285268, 2366280,
+    831420, 3533052, 3899788, 4835568, 3443805, 5028804, 4492562, 364199, 699880, 4583916, 2398968,
+    1697915, 4807767, 2043339, 2085351, 1315688, 724049, 4975733, 2799932, 894916, 4810308,
+    3386452, 4857659, 392403, 628306, 6344045, 5865778, 3884446, 4818740, 2197129, 5333552,
+    1965763, 574070, 5580860, 2280724, 834681, 87019, 5023124, 2631717, 522592, 1666624, 4950750,
+    3001484, 4659282, 4423839, 153000, 2553274, 4832852, 1681782, 2471318, 1686220, 4178214,
+    6436539, 6625875, 1321244, 2210441, 4602838, 69301, 2071121, 1102824, 3782309, 3607719,
+    5996221, 6512247, 1978006, 6016524, 5548518, 3357588, 3416458, 2330099, 1030056, 1664140,
+    478786, 3334507, 4630096, 738061, 1789694, 3886118, 4187828, 4358134, 5975204, 5931304,
+    6601291, 3731364, 3193334, 2349977, 3075154, 5586454, 2828631, 4648532, 984501, 230457,
+    1002250, 4332418, 591293, 5844591, 4789893, 634200, 843129, 4618620, 1604472, 219403, 1329394,
+    768746, 2383594, 4092694, 106774, 6492498, 1902315, 5163134, 5537937, 2099131, 4993555,
+    3192675, 5358432, 1260101, 2191088, 5191477, 6342872, 3363198, 6232676, 1755866, 2064300,
+    4116220, 3818479, 54264, 3869176, 4267045, 3060200, 3322369, 240984, 2520282, 2333905, 529305,
+    3105644, 3232331, 292869, 1800814, 2433529, 621206, 5650182, 5017252, 330792, 1968745, 4061606,
+    313514, 3360342, 252251, 5207342, 4779575, 5096077, 2140987, 507300, 168563, 845036, 5175842,
+    1191167, 3594559, 4843694, 5511957, 2237671, 1680242, 2795528, 5067830, 1330443, 5041371,
+    4843793, 4783880, 3247398, 2600686, 1400647, 3765591, 6326699, 1957707, 6657061, 6589043,
+    1701462, 2566542, 1247072, 1953129, 6206273, 4983733, 5515135, 4177614, 5061289, 3536743,
+    6222864, 2649461, 6636313, 5540487, 4029135, 4627775, 5293303, 266854, 1298704, 843827,
+    2081493, 1408985, 2005060, 3595255, 6438984, 3167781, 5206070, 3839408, 895767, 4995601,
+    5937928, 4916905, 6322789, 5721686, 362371, 2674404, 4111440, 6177280, 1331176, 1053186,
+    473941, 575947, 1778568, 2260805, 6227802, 3893161, 3035779, 1194990, 4144702, 2522666,
+    5566594, 5530236, 2291456, 321189, 1425252, 6541782, 1051829, 4765539, 1935685, 4878771,
+    371258, 1039796, 4373329, 4332562, 921353, 6028386, 2610102, 3493435, 686101, 6501225, 146859,
+    4731710, 830388, 458196, 5816987, 1228411, 4314829, 4497044, 1896857, 765841, 5209552, 4980774,
+    4869864, 2781913, 29699, 3167299, 2985467, 5757618, 4614191, 5559088, 3705152, 455834, 1518905,
+    1702269, 281853, 2587811, 2575092, 2793085, 5552115,
+];
+
+pub(crate) const HOTS_INV_NTT_TABLE: [u32; N] = [
+    1, 2787255, 5140936, 4056432, 2782840, 6214181, 2579062, 1298972, 3144104, 6104562, 1866836,
+    1337363, 3440051, 5629317, 694326, 5283698, 1355056, 5001634, 5043439, 1574367, 986703,
+    2948821, 2071820, 140863, 1898927, 3099888, 1140679, 2621749, 1368259, 3192638, 1098540,
+    6036976, 6324616, 2402997, 5248819, 6165115, 3705480, 2575647, 3723123, 3979627, 6481638,
+    2904971, 6166436, 1207799, 531363, 2219618, 4604030, 3941553, 3862805, 4056848, 2429619,
+    2952128, 1716184, 740463, 1434869, 5615785, 6435984, 3776592, 5439333, 3544677, 2118004,
+    3633532, 3542413, 4213219, 3284971, 1791936, 1359138, 1224444, 6521442, 5399668, 5967135,
+    2210306, 1311493, 2754237, 1983234, 3455786, 3612387, 3389073, 4423880, 5123216, 5907936,
+    2327359, 4484558, 3289900, 2170967, 2188447, 824071, 5263065, 1887497, 5866205, 1493600,
+    6571427, 1038722, 1435825, 121819, 1409137, 3921322, 4387177, 5360789, 5043057, 4795565,
+    1770445, 3677077, 3518107, 370686, 3958205, 6186124, 5177291, 938287, 4572082, 1989958,
+    5896919, 5744018, 2010128, 1236620, 6438658, 2501102, 3690413, 3115453, 5383821, 1050765,
+    54008, 4553556, 239552, 501242, 1520783, 1113551, 2315531, 3894981, 1719180, 5970864, 5379225,
+    4609562, 4651574, 1887146, 4996998, 4295945, 2110997, 5995033, 6330714, 2202351, 1666109,
+    3251108, 1859345, 2795125, 3161861, 5863493, 4328633, 1409645, 3177078, 6235609, 3893340,
+    5804594, 3758061, 4697561, 5471651, 4602515, 5740331, 6580310, 6599204, 5412972, 5385797,
+    1991642, 6500326, 1258914, 3319162, 3821665, 161099, 2732265, 2185771, 3382130, 6164544,
+    3469022, 1153316, 3716584, 1672368, 452762, 5522375, 62522, 2866633, 1662219, 1134759, 1323436,
+    3833266, 4853884, 5902063, 6329695, 5825060, 6420716, 834280, 4788497, 4581064, 3684470,
+    2752108, 1689192, 4585797, 4455648, 3720718, 4936199, 4128878, 1612894, 6515252, 1180024,
+    4494784, 2280761, 1590687, 3331138, 4765661, 5454613, 6459484, 5864443, 1035335, 5945150,
+    2947820, 5442974, 2452024, 648301, 3100316, 5665163, 315980, 6274165, 2553644, 816303, 5214867,
+    1437767, 2320784, 2991566, 4251437, 1357003, 2212676, 5550896, 6145044, 3379212, 578836,
+    4234556, 3900256, 4318933, 5211527, 532612, 2146353, 1642387, 4190240, 3110761, 2748450,
+    3642414, 3187545, 5198, 359758, 2274957, 5967388, 4565835, 548441, 4401733, 2884591, 769959,
+    4320569, 1142798, 3901828, 4119821, 4107102, 6413060, 4992644, 5176008, 6239079, 2989761,
+    1135825, 2080722, 937295, 3709446, 3527614, 6665214, 3913000, 1825049, 1714139, 1485361,
+    5929072, 4798056, 2197869, 2380084, 5466502, 877926, 6236717, 5864525, 1963203, 6548054,
+    193688, 6008812, 3201478, 4084811, 666527, 5773560, 2362351, 2321584, 5655117, 6323655,
+    1816142, 4759228, 1929374, 5643084, 153131, 5269661, 6373724, 4403457, 1164677, 1128319,
+    4172247, 2550211, 5499923, 3659134, 2801752, 467111, 4434108, 4916345, 6118966, 6220972,
+    5641727, 5363737, 517633, 2583473, 4020509, 6332542, 973227, 372124, 1778008, 756985, 1699312,
+    5799146, 2855505, 1488843, 3527132, 255929, 3099658, 4689853, 5285928, 4613420, 5851086,
+    5396209, 6428059, 1401610, 2067138, 2665778, 1154426, 58600, 4045452, 472049, 3158170, 1633624,
+    2517299, 1179778, 1711180, 488640, 4741784, 5447841, 4128371, 4993451, 105870, 37852, 4737206,
+    368214, 2929322, 5294266, 4094227, 3447515, 1911033, 1851120, 1653542, 5364470, 1627083,
+    3899385, 5014671, 4457242, 1182956, 1851219, 3100354, 5503746, 1519071, 5849877, 6526350,
+    6187613, 4553926, 1598836, 1915338, 1487571, 6442662, 3334571, 6381399, 2633307, 4726168,
+    6364121, 1677661, 1044731, 6073707, 4261384, 4894099, 6402044, 3462582, 3589269, 6165608,
+    4361008, 4174631, 6453929, 3372544, 3634713, 2427868, 2825737, 6640649, 2876434, 2578693,
+    4630613, 4939047, 462237, 3331715, 352041, 1503436, 4503825, 5434812, 1336481, 3502238,
+    1701358, 4595782, 1156976, 1531779, 4792598, 202415, 6588139, 2602219, 4311319, 5926167,
+    5365519, 6475510, 5090441, 2076293, 5851784, 6060713, 1905020, 850322, 6103620, 2362495,
+    5692663, 6464456, 5710412, 2046381, 3866282, 1108459, 3619759, 4344936, 3501579, 2963549,
+    93622, 763609, 719709, 2336779, 2507085, 2808795, 4905219, 5956852, 2064817, 3360406, 6216127,
+    5030773, 5664857, 4364814, 3278455, 3337325, 1146395, 678389, 4716907, 182666, 698692, 3087194,
+    2912604, 5592089, 4623792, 6625612, 2092075, 4484472, 5373669, 69038, 258374, 2516699, 5008693,
+    4223595, 5013131, 1862061, 4141639, 6541913, 2271074, 2035631, 3693429, 1744163, 5028289,
+    6172321, 4063196, 1671789, 6607894, 5860232, 4414189, 1114053, 6120843, 4729150, 1361361,
+    4497784, 1876173, 2810467, 829135, 350868, 6066607, 6302510, 1837254, 3308461, 1884605,
+    5799997,
+];
@@ -0,0 +1,627 @@
+//! Command-line interface to the synchronized multi-signature scheme.
+//!
+//! Parameters, keys and signatures are stored in files with the canonical
+//! binary encoding of the crate. Each command prints a JSON object on stdout;
+//! failures print a JSON object with an `error` field on stderr and exit with
+//! one of the codes below.
+
+use clap::{Args, Parser, Subcommand};
+use serde_json::{json, Value};
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+use sync_multi_sig::{
+    Error, MultiSig, Progress, SMSigPK, SMSigParam, SMSigSK, SMSigScheme, SMSignature,
+    StatefulSigner, DEFAULT_SETUP_SEED, HEIGHT,
+};
+use zeroize::Zeroizing;
+
+/// a signature does not verify
+const EXIT_INVALID: u8 = 1;
+/// an input file cannot be decoded, or does not match the other inputs;
+/// command line errors exit with 2
+const EXIT_MALFORMED: u8 = 3;
+/// a file cannot be read or written
+const EXIT_IO: u8 = 4;
+
+#[derive(Parser)]
+#[command(
+    name = "squirrel",
+    about = "Synchronized multi-signatures from lattices"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Derive the public parameters from a public seed
+    Setup {
+        /// the public seed, the crate's default one if absent
+        #[arg(long)]
+        seed: Option<String>,
+        #[arg(long, default_value_t = HEIGHT)]
+        height: usize,
+        #[arg(long)]
+        out: PathBuf,
+    },
+    /// Generate a key pair from a file holding a 32-byte secret seed
+    Keygen {
+        #[arg(long)]
+        param: PathBuf,
+        #[arg(long)]
+        seed: PathBuf,
+        #[arg(long)]
+        pk: PathBuf,
+        /// a new file holding the secret seed, as `--seed`
+        #[arg(long)]
+        sk: PathBuf,
+        /// a new file holding the tree of the one-time public keys
+        #[arg(long)]
+        tree: PathBuf,
+        /// a new file recording the used one-time key indices
+        #[arg(long)]
+        state: PathBuf,
+        /// report the progress on stderr, one JSON object per percent
+        #[arg(long)]
+        progress: bool,
+    },
+    /// Sign a message with the one-time key at an index that has not been used,
+    /// and record the index in the state file of the key
+    Sign {
+        #[arg(long)]
+        param: PathBuf,
+        #[arg(long)]
+        sk: PathBuf,
+        /// the tree file written by keygen
+        #[arg(long)]
+        tree: PathBuf,
+        /// the state file written by keygen
+        #[arg(long)]
+        state: PathBuf,
+        #[arg(long)]
+        index: usize,
+        #[command(flatten)]
+        message: Message,
+        #[arg(long)]
+        out: PathBuf,
+    },
+    /// Verify a signature of a single signer
+    Verify {
+        #[arg(long)]
+        param: PathBuf,
+        #[arg(long)]
+        pk: PathBuf,
+        #[command(flatten)]
+        message: Message,
+        #[arg(long)]
+        sig: PathBuf,
+    },
+    /// Aggregate signatures of a message; the i-th `--pk` belongs to the i-th `--sig`
+    Aggregate {
+        #[arg(long = "sig", required = true)]
+        sigs: Vec<PathBuf>,
+        #[arg(long = "pk", required = true)]
+        pks: Vec<PathBuf>,
+        #[command(flatten)]
+        message: Message,
+        #[arg(long)]
+        out: PathBuf,
+    },
+    /// Verify an aggregated signature against the public keys in aggregation order
+    BatchVerify {
+        #[arg(long)]
+        param: PathBuf,
+        #[arg(long = "pk", required = true)]
+        pks: Vec<PathBuf>,
+        #[command(flatten)]
+        message: Message,
+        #[arg(long)]
+        sig: PathBuf,
+    },
+}
+
+#[derive(Args)]
+#[group(required = true, multiple = false)]
+struct Message {
+    /// the message
+    #[arg(long)]
+    message: Option<String>,
+    /// a file holding the message
+    #[arg(long)]
+    message_file: Option<PathBuf>,
+}
+
+impl Message {
+    fn read(&self) -> Result<Vec<u8>, Failure> {
+        match (&self.message, &self.message_file) {
+            (Some(message), _) => Ok(message.as_bytes().to_vec()),
+            (None, Some(path)) => read(path),
+            (None, None) => unreachable!("clap requires one of the message arguments"),
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Failure {
+    Invalid,
+    Malformed(String),
+    Io(String),
+}
+
+impl Failure {
+    fn exit_code(&self) -> u8 {
+        match self {
+            Failure::Invalid => EXIT_INVALID,
+            Failure::Malformed(_) => EXIT_MALFORMED,
+            Failure::Io(_) => EXIT_IO,
+        }
+    }
+
+    fn message(&self) -> String {
+        match self {
+            Failure::Invalid => "invalid signature".to_string(),
+            Failure::Malformed(message) | Failure::Io(message) => message.clone(),
+        }
+    }
+
+    /// Prefix the message with the file that caused the failure.
+    fn in_file(self, path: &Path) -> Self {
+        match self {
+            Failure::Invalid => self,
+            Failure::Malformed(message) => {
+                Failure::Malformed(format!("{}: {}", path.display(), message))
+            }
+            Failure::Io(message) => Failure::Io(format!("{}: {}", path.display(), message)),
+        }
+    }
+}
+
+impl From<Error> for Failure {
+    fn from(e: Error) -> Self {
+        match e {
+            Error::Io(_) | Error::StateLocked => Failure::Io(e.to_string()),
+            _ => Failure::Malformed(e.to_string()),
+        }
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn read(path: &Path) -> Result<Vec<u8>, Failure> {
+    fs::read(path).map_err(|e| Failure::Io(format!("{}: {}", path.display(), e)))
+}
+
+fn write(path: &Path, bytes: &[u8]) -> Result<(), Failure> {
+    fs::write(path, bytes).map_err(|e| Failure::Io(format!("{}: {}", path.display(), e)))
+}
+
+/// Write a secret to a new file that only its owner can read; an existing
+/// file is never overwritten.
+fn write_secret(path: &Path, bytes: &[u8]) -> Result<(), Failure> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    options
+        .open(path)
+        .and_then(|mut file| {
+            file.write_all(bytes)?;
+            file.sync_all()
+        })
+        .map_err(|e| Failure::Io(format!("{}: {}", path.display(), e)))
+}
+
+fn read_seed(path: &Path) -> Result<Zeroizing<[u8; 32]>, Failure> {
+    let bytes = Zeroizing::new(read(path)?);
+    let seed: [u8; 32] = bytes.as_slice().try_into().map_err(|_| {
+        Failure::Malformed(format!(
+            "{}: expected a 32-byte seed, found {} bytes",
+            path.display(),
+            bytes.len()
+        ))
+    })?;
+    Ok(Zeroizing::new(seed))
+}
+
+fn load<T>(path: &Path, decode: fn(&[u8]) -> sync_multi_sig::Result<T>) -> Result<T, Failure> {
+    decode(&read(path)?).map_err(|e| Failure::Malformed(format!("{}: {}", path.display(), e)))
+}
+
+fn load_pks(paths: &[PathBuf]) -> Result<Vec<SMSigPK>, Failure> {
+    paths.iter().map(|p| load(p, SMSigPK::from_bytes)).collect()
+}
+
+/// Print a report on stderr each time a stage reaches a new percent; with the
+/// `parallel` feature the reports arrive from several threads.
+fn report_progress() -> impl Fn(Progress) + Sync {
+    // the last printed stage and percent, as `101 * stage + percent + 1`
+    let last = AtomicUsize::new(0);
+    move |p: Progress| {
+        let key = 101 * p.stage as usize + 100 * p.done / p.total + 1;
+        if last.fetch_max(key, Ordering::Relaxed) < key {
+            let stage = format!("{:?}", p.stage).to_lowercase();
+            eprintln!(
+                "{}",
+                json!({ "stage": stage, "done": p.done, "total": p.total })
+            );
+        }
+    }
+}
+
+fn verified(valid: bool) -> Result<Value, Failure> {
+    if valid {
+        Ok(json!({ "valid": true }))
+    } else {
+        Err(Failure::Invalid)
+    }
+}
+
+fn run(cli: Cli) -> Result<Value, Failure> {
+    match cli.command {
+        Command::Setup { seed, height, out } => {
+            let seed = seed.map_or(DEFAULT_SETUP_SEED.to_vec(), String::into_bytes);
+            let pp = SMSigParam::setup_from_seed(&seed, height)?;
+            write(&out, &pp.to_bytes())?;
+            Ok(json!({
+                "height": pp.height(),
+                "fingerprint": hex(&pp.fingerprint()),
+            }))
+        }
+        Command::Keygen {
+            param,
+            seed,
+            pk,
+            sk,
+            tree,
+            state,
+            progress,
+        } => {
+            let pp = load(&param, SMSigParam::from_bytes)?;
+            let seed = read_seed(&seed)?;
+            let (root, key) = if progress {
+                SMSigScheme::key_gen_with_progress(&seed, &pp, &report_progress())
+            } else {
+                SMSigScheme::key_gen(&seed, &pp)
+            };
+            write(&pk, &root.to_bytes())?;
+            // the key is the seed and the tree, which is public and far larger
+            write_secret(&sk, seed.as_slice())?;
+            key.save_tree(&tree, &pp)
+                .map_err(|e| Failure::from(e).in_file(&tree))?;
+            StatefulSigner::create(key, &state).map_err(|e| Failure::from(e).in_file(&state))?;
+            Ok(json!({ "num_indices": 1usize << (pp.height() - 1) }))
+        }
+        Command::Sign {
+            param,
+            sk,
+            tree,
+            state,
+            index,
+            message,
+            out,
+        } => {
+            let pp = load(&param, SMSigParam::from_bytes)?;
+            // the tree is mapped rather than read, and keygen never rewrites it
+            let sk = SMSigSK::open_tree(&*read_seed(&sk)?, &tree, &pp)
+                .map_err(|e| Failure::from(e).in_file(&tree))?;
+            let message = message.read()?;
+            let mut signer =
+                StatefulSigner::open(sk, &state).map_err(|e| Failure::from(e).in_file(&state))?;
+            let sig = signer.sign(index, &message, &pp)?;
+            write(&out, &sig.to_bytes())?;
+            Ok(json!({ "index": sig.index() }))
+        }
+        Command::Verify {
+            param,
+            pk,
+            message,
+            sig,
+        } => {
+            let pp = load(&param, SMSigParam::from_bytes)?;
+            let pk = load(&pk, SMSigPK::from_bytes)?;
+            let sig = load(&sig, SMSignature::from_bytes)?;
+            if sig.is_aggregated() {
+                return Err(Failure::Malformed(
+                    "aggregated signatures are checked with batch-verify".to_string(),
+                ));
+            }
+            verified(SMSigScheme::verify(&pk, &message.read()?, &sig, &pp))
+        }
+        Command::Aggregate {
+            sigs,
+            pks,
+            message,
+            out,
+        } => {
+            let sigs = sigs
+                .iter()
+                .map(|p| load(p, SMSignature::from_bytes))
+                .collect::<Result<Vec<_>, _>>()?;
+            let pks = load_pks(&pks)?;
+            let agg_sig = SMSigScheme::aggregate(&sigs, &pks, &message.read()?)?;
+            write(&out, &agg_sig.to_bytes())?;
+            Ok(json!({
+                "index": agg_sig.index(),
+                "num_signers": sigs.len(),
+            }))
+        }
+        Command::BatchVerify {
+            param,
+            pks,
+            message,
+            sig,
+        } => {
+            let pp = load(&param, SMSigParam::from_bytes)?;
+            let pks = load_pks(&pks)?;
+            let sig = load(&sig, SMSignature::from_bytes)?;
+            if !sig.is_aggregated() {
+                return Err(Failure::Malformed(
+                    "single signatures are checked with verify".to_string(),
+                ));
+            }
+            verified(SMSigScheme::batch_verify(&pks, &message.read()?, &sig, &pp))
+        }
+    }
+}
+
+// parameters and signatures are hundreds of kilobytes each, and unoptimized
+// builds keep several copies of them on the stack while aggregating
+fn run_with_large_stack(cli: Cli) -> Result<Value, Failure> {
+    std::thread::Builder::new()
+        .stack_size(32 << 20)
+        .spawn(|| run(cli))
+        .expect("cannot spawn the command thread")
+        .join()
+        .unwrap_or_else(|e| std::panic::resume_unwind(e))
+}
+
+fn main() -> ExitCode {
+    match run_with_large_stack(Cli::parse()) {
+        Ok(output) => {
+            println!("{}", output);
+            ExitCode::SUCCESS
+        }
+        Err(failure) => {
+            if let Failure::Invalid = failure {
+                println!("{}", json!({ "valid": false }));
+            }
+            eprintln!("{}", json!({ "error": failure.message() }));
+            ExitCode::from(failure.exit_code())
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn run_args(args: &[&str]) -> Result<Value, Failure> {
+        run_with_large_stack(
+            Cli::try_parse_from(std::iter::once("squirrel").chain(args.iter().copied())).unwrap(),
+        )
+    }
+
+    fn exit_code(args: &[&str]) -> u8 {
+        run_args(args).map_or_else(|f| f.exit_code(), |_| 0)
+    }
+
+    #[test]
+    fn test_cli() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = |name: &str| dir.path().join(name).to_str().unwrap().to_string();
+        let (pp, agg) = (file("pp"), file("agg"));
+
+        let output = run_args(&["setup", "--height", "3", "--out", &pp]).unwrap();
+        assert_eq!(output["height"], 3);
+
+        let mut sigs = Vec::new();
+        let mut pks = Vec::new();
+        for i in 0..3u8 {
+            let (seed, pk, sk, tree, state, sig) = (
+                file(&format!("seed{}", i)),
+                file(&format!("pk{}", i)),
+                file(&format!("sk{}", i)),
+                file(&format!("tree{}", i)),
+                file(&format!("state{}", i)),
+                file(&format!("sig{}", i)),
+            );
+            fs::write(&seed, [i; 32]).unwrap();
+            let output = run_args(&[
+                "keygen",
+                "--param",
+                &pp,
+                "--seed",
+                &seed,
+                "--pk",
+                &pk,
+                "--sk",
+                &sk,
+                "--tree",
+                &tree,
+                "--state",
+                &state,
+                "--progress",
+            ])
+            .unwrap();
+            assert_eq!(output["num_indices"], 4);
+            let sign = |index: &str| {
+                exit_code(&[
+                    "sign",
+                    "--param",
+                    &pp,
+                    "--sk",
+                    &sk,
+                    "--tree",
+                    &tree,
+                    "--state",
+                    &state,
+                    "--index",
+                    index,
+                    "--message",
+                    "hello",
+                    "--out",
+                    &sig,
+                ])
+            };
+            assert_eq!(sign("1"), 0);
+            assert_eq!(
+                exit_code(&[
+                    "verify",
+                    "--param",
+                    &pp,
+                    "--pk",
+                    &pk,
+                    "--message",
+                    "hello",
+                    "--sig",
+                    &sig
+                ]),
+                0
+            );
+            assert_eq!(
+                exit_code(&[
+                    "verify",
+                    "--param",
+                    &pp,
+                    "--pk",
+                    &pk,
+                    "--message",
+                    "bye",
+                    "--sig",
+                    &sig
+                ]),
+                EXIT_INVALID
+            );
+            // used and reserved indices are refused, and keys are not
+            // overwritten
+            if i == 0 {
+                #[cfg(unix)]
+                {
+                    use std::os::unix::fs::PermissionsExt;
+                    let mode = fs::metadata(&sk).unwrap().permissions().mode();
+                    assert_eq!(mode & 0o777, 0o600);
+                }
+                let sk_bytes = fs::read(&sk).unwrap();
+                assert_eq!(
+                    exit_code(&[
+                        "keygen",
+                        "--param",
+                        &pp,
+                        "--seed",
+                        &seed,
+                        "--pk",
+                        &pk,
+                        "--sk",
+                        &sk,
+                        "--tree",
+                        &file("other_tree"),
+                        "--state",
+                        &file("other"),
+                    ]),
+                    EXIT_IO
+                );
+                assert_eq!(fs::read(&sk).unwrap(), sk_bytes);
+                // the key file only holds the seed
+                assert_eq!(sk_bytes, [i; 32]);
+                assert_eq!(sign("1"), EXIT_MALFORMED);
+                assert_eq!(sign("3"), EXIT_MALFORMED);
+
+                // the state cannot be used by two signers at once
+                let pp = SMSigParam::from_bytes(&fs::read(&pp).unwrap()).unwrap();
+                let key = SMSigSK::open_tree(&[i; 32], &tree, &pp).unwrap();
+                let signer = StatefulSigner::open(key, &state).unwrap();
+                assert_eq!(sign("2"), EXIT_IO);
+                drop(signer);
+            }
+            sigs.extend(["--sig".to_string(), sig]);
+            pks.extend(["--pk".to_string(), pk]);
+        }
+        let sigs: Vec<&str> = sigs.iter().map(String::as_str).collect();
+        let pks: Vec<&str> = pks.iter().map(String::as_str).collect();
+
+        let mut args = vec!["aggregate", "--message", "hello", "--out", &agg];
+        args.extend(&sigs);
+        args.extend(&pks);
+        let output = run_args(&args).unwrap();
+        assert_eq!(output["index"], 1);
+        assert_eq!(output["num_signers"], 3);
+
+        let batch_verify = |message: &str, pks: &[&str]| {
+            let mut args = vec![
+                "batch-verify",
+                "--param",
+                &pp,
+                "--message",
+                message,
+                "--sig",
+                &agg,
+            ];
+            args.extend(pks);
+            exit_code(&args)
+        };
+        assert_eq!(batch_verify("hello", &pks), 0);
+        assert_eq!(batch_verify("bye", &pks), EXIT_INVALID);
+        assert_eq!(batch_verify("hello", &pks[..4]), EXIT_INVALID);
+
+        // malformed and missing inputs
+        let mut args = vec!["aggregate", "--message", "hello", "--out", &agg];
+        args.extend(&sigs);
+        args.extend(&pks[..4]);
+        assert_eq!(exit_code(&args), EXIT_MALFORMED);
+        assert_eq!(
+            exit_code(&[
+                "verify",
+                "--param",
+                &pp,
+                "--pk",
+                &pp,
+                "--message",
+                "hello",
+                "--sig",
+                &agg
+            ]),
+            EXIT_MALFORMED
+        );
+        assert_eq!(
+            exit_code(&[
+                "keygen",
+                "--param",
+                &pp,
+                "--seed",
+                &pp,
+                "--pk",
+                &file("pk"),
+                "--sk",
+                &file("sk"),
+                "--tree",
+                &file("tree"),
+                "--state",
+                &file("state"),
+            ]),
+            EXIT_MALFORMED
+        );
+        assert_eq!(
+            exit_code(&[
+                "verify",
+                "--param",
+                &file("missing"),
+                "--pk",
+                pks[1],
+                "--message",
+                "hello",
+                "--sig",
+                sigs[1]
+            ]),
+            EXIT_IO
+        );
+        assert!(Cli::try_parse_from(["squirrel", "verify", "--param", &pp]).is_err());
+    }
+}
@@ -0,0 +1,373 @@
+//! Canonical binary encoding.
+//!
+//! Every top-level object is encoded as a version byte followed by its fields
+//! in declaration order. Integers are little endian. Decoding rejects
+//! non-canonical inputs, i.e., coefficients out of range, malformed flags
+//! and trailing bytes.
+
+use crate::{
+    error::{Error, Result},
+    LargePoly, SignedPoly, LARGE_MODULUS, SMALL_MODULUS,
+};
+
+/// Version of the binary encoding.
+pub const ENCODING_VERSION: u8 = 1;
+
+pub(crate) trait Encode {
+    fn encode(&self, out: &mut Vec<u8>);
+}
+
+/// Decoding happens in place, since most of the objects are large arrays of
+/// polynomials that we do not want to move around on the stack.
+pub(crate) trait Decode {
+    fn decode_into(&mut self, reader: &mut Reader) -> Result<()>;
+}
+
+/// A cursor over the bytes to be decoded.
+pub(crate) struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    pub(crate) fn remaining(&self) -> usize {
+        self.bytes.len()
+    }
+
+    pub(crate) fn read_bytes(&mut self, len: usize) -> Result<&'a [u8]> {
+        if self.bytes.len() < len {
+            return Err(Error::UnexpectedEnd);
+        }
+        let (res, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(res)
+    }
+
+    pub(crate) fn read_array<const LEN: usize>(&mut self) -> Result<[u8; LEN]> {
+        let mut res = [0u8; LEN];
+        res.copy_from_slice(self.read_bytes(LEN)?);
+        Ok(res)
+    }
+
+    pub(crate) fn read_u8(&mut self) -> Result<u8> {
+        Ok(self.read_array::<1>()?[0])
+    }
+
+    pub(crate) fn read_u16(&mut self) -> Result<u16> {
+        Ok(u16::from_le_bytes(self.read_array()?))
+    }
+
+    pub(crate) fn read_u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.read_array()?))
+    }
+
+    pub(crate) fn read_i32(&mut self) -> Result<i32> {
+        Ok(i32::from_le_bytes(self.read_array()?))
+    }
+
+    pub(crate) fn read_u64(&mut self) -> Result<u64> {
+        Ok(u64::from_le_bytes(self.read_array()?))
+    }
+
+    pub(crate) fn read_bool(&mut self) -> Result<bool> {
+        match self.read_u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            b => Err(Error::InvalidFlag(b)),
+        }
+    }
+}
+
+impl Encode for bool {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.push(*self as u8)
+    }
+}
+
+impl<T: Encode, const LEN: usize> Encode for [T; LEN] {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.iter().for_each(|x| x.encode(out))
+    }
+}
+
+impl<T: Decode, const LEN: usize> Decode for [T; LEN] {
+    fn decode_into(&mut self, reader: &mut Reader) -> Result<()> {
+        self.iter_mut().try_for_each(|x| x.decode_into(reader))
+    }
+}
+
+/// Encode an object with the version prefix.
+pub(crate) fn to_bytes<T: Encode>(obj: &T) -> Vec<u8> {
+    let mut out = vec![ENCODING_VERSION];
+    obj.encode(&mut out);
+    out
+}
+
+/// Decode an object with the version prefix, rejecting trailing bytes.
+pub(crate) fn from_bytes<T: Decode + Default>(bytes: &[u8]) -> Result<T> {
+    let mut reader = Reader::new(bytes);
+    let version = reader.read_u8()?;
+    if version != ENCODING_VERSION {
+        return Err(Error::UnsupportedVersion(version));
+    }
+    let mut res = T::default();
+    res.decode_into(&mut reader)?;
+    if reader.remaining() != 0 {
+        return Err(Error::TrailingBytes(reader.remaining()));
+    }
+    Ok(res)
+}
+
+/// Check the coefficients of a decomposed (and possibly randomized) polynomial vector.
+/// Fresh decompositions are binary; randomized ones are reduced modulo the HVC modulus
+/// when hashed, so larger coefficients are never canonical.
+pub(crate) fn check_decomposed(polys: &[SignedPoly], is_randomized: bool) -> Result<()> {
+    for &c in polys.iter().flat_map(|x| x.coeffs.iter()) {
+        let valid = if is_randomized {
+            c.unsigned_abs() < SMALL_MODULUS as u32
+        } else {
+            c == 0 || c == 1
+        };
+        if !valid {
+            return Err(Error::InvalidCoefficient(c as i64));
+        }
+    }
+    Ok(())
+}
+
+/// The range of coefficients accepted by the compact encoding.
+/// A coefficient `c` is packed as `c - min` with `width` bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct CoeffRange {
+    min: i32,
+    max: i32,
+    width: u32,
+}
+
+impl CoeffRange {
+    /// Coefficients in {0, 1}, i.e., fresh binary decompositions.
+    pub(crate) const BINARY: Self = Self {
+        min: 0,
+        max: 1,
+        width: 1,
+    };
+
+    /// Coefficients in `[-bound, bound]`.
+    pub(crate) fn signed(bound: u32) -> Self {
+        let bound = bound.min(i32::MAX as u32 / 2);
+        Self {
+            min: -(bound as i32),
+            max: bound as i32,
+            width: u32::BITS - (2 * bound).leading_zeros(),
+        }
+    }
+
+    /// Number of bits used for each coefficient.
+    pub(crate) fn width(&self) -> u32 {
+        self.width
+    }
+}
+
+/// Packs values with arbitrary bit widths, least significant bit first.
+#[derive(Debug, Default)]
+pub(crate) struct BitWriter {
+    out: Vec<u8>,
+    acc: u64,
+    bits: u32,
+}
+
+impl BitWriter {
+    pub(crate) fn new(out: Vec<u8>) -> Self {
+        Self {
+            out,
+            acc: 0,
+            bits: 0,
+        }
+    }
+
+    /// Write the lowest `width` bits of `value`; `width` is at most 32.
+    pub(crate) fn write(&mut self, value: u32, width: u32) {
+        self.acc |= ((value as u64) & ((1 << width) - 1)) << self.bits;
+        self.bits += width;
+        while self.bits >= 8 {
+            self.out.push(self.acc as u8);
+            self.acc >>= 8;
+            self.bits -= 8;
+        }
+    }
+
+    pub(crate) fn write_coeffs(&mut self, coeffs: &[i32], range: CoeffRange) -> Result<()> {
+        for &c in coeffs {
+            if c < range.min || c > range.max {
+                return Err(Error::InvalidCoefficient(c as i64));
+            }
+            self.write((c - range.min) as u32, range.width);
+        }
+        Ok(())
+    }
+
+    /// Write the coefficients of a HOTS polynomial, lifted to `[-(q-1)/2, (q-1)/2]`.
+    pub(crate) fn write_large_poly(&mut self, poly: &LargePoly, range: CoeffRange) -> Result<()> {
+        for &e in poly.coeffs.iter() {
+            let c = if e > LARGE_MODULUS / 2 {
+                e as i32 - LARGE_MODULUS as i32
+            } else {
+                e as i32
+            };
+            self.write_coeffs(&[c], range)?;
+        }
+        Ok(())
+    }
+
+    /// Flush the last partial byte, padded with zeros.
+    pub(crate) fn finish(mut self) -> Vec<u8> {
+        if self.bits > 0 {
+            self.out.push(self.acc as u8);
+        }
+        self.out
+    }
+}
+
+/// Reads values written by a `BitWriter`.
+pub(crate) struct BitReader<'a> {
+    bytes: &'a [u8],
+    acc: u64,
+    bits: u32,
+}
+
+impl<'a> BitReader<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            acc: 0,
+            bits: 0,
+        }
+    }
+
+    pub(crate) fn read(&mut self, width: u32) -> Result<u32> {
+        while self.bits < width {
+            let (&b, rest) = self.bytes.split_first().ok_or(Error::UnexpectedEnd)?;
+            self.bytes = rest;
+            self.acc |= (b as u64) << self.bits;
+            self.bits += 8;
+        }
+        let res = (self.acc & ((1 << width) - 1)) as u32;
+        self.acc >>= width;
+        self.bits -= width;
+        Ok(res)
+    }
+
+    pub(crate) fn read_coeffs(&mut self, coeffs: &mut [i32], range: CoeffRange) -> Result<()> {
+        for c in coeffs.iter_mut() {
+            let value = self.read(range.width)? as i64 + range.min as i64;
+            if value > range.max as i64 {
+                return Err(Error::InvalidCoefficient(value));
+            }
+            *c = value as i32;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn read_large_poly(
+        &mut self,
+        poly: &mut LargePoly,
+        range: CoeffRange,
+    ) -> Result<()> {
+        let mut c = [0i32];
+        for e in poly.coeffs.iter_mut() {
+            self.read_coeffs(&mut c, range)?;
+            *e = if c[0] < 0 {
+                (c[0] + LARGE_MODULUS as i32) as u32
+            } else {
+                c[0] as u32
+            };
+        }
+        Ok(())
+    }
+
+    /// Check that the padding bits are zero and that there are no trailing bytes.
+    pub(crate) fn finish(self) -> Result<()> {
+        if !self.bytes.is_empty() {
+            return Err(Error::TrailingBytes(self.bytes.len()));
+        }
+        if self.acc != 0 {
+            return Err(Error::InvalidPadding);
+        }
+        Ok(())
+    }
+}
+
+/// Implements `to_bytes`, `from_bytes` and the serde traits for a type
+/// that implements `Encode` and `Decode`.
+macro_rules! impl_serialization {
+    ($type: ty) => {
+        impl $type {
+            /// Serialize into the canonical binary encoding.
+            pub fn to_bytes(&self) -> Vec<u8> {
+                crate::codec::to_bytes(self)
+            }
+
+            /// Deserialize from the canonical binary encoding.
+            pub fn from_bytes(bytes: &[u8]) -> crate::error::Result<Self> {
+                crate::codec::from_bytes(bytes)
+            }
+        }
+
+        impl serde::Serialize for $type {
+            fn serialize<S: serde::Serializer>(
+                &self,
+                serializer: S,
+            ) -> core::result::Result<S::Ok, S::Error> {
+                serializer.serialize_bytes(&self.to_bytes())
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $type {
+            fn deserialize<D: serde::Deserializer<'de>>(
+                deserializer: D,
+            ) -> core::result::Result<Self, D::Error> {
+                let bytes = deserializer.deserialize_byte_buf(crate::codec::BytesVisitor)?;
+                Self::from_bytes(&bytes).map_err(serde::de::Error::custom)
+            }
+        }
+    };
+}
+
+pub(crate) use impl_serialization;
+
+/// Accepts both byte strings and sequences of bytes,
+/// since self-describing formats such as JSON encode bytes as a sequence.
+pub(crate) struct BytesVisitor;
+
+impl<'de> serde::de::Visitor<'de> for BytesVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "a byte string")
+    }
+
+    fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> core::result::Result<Self::Value, E> {
+        Ok(v.to_vec())
+    }
+
+    fn visit_byte_buf<E: serde::de::Error>(
+        self,
+        v: Vec<u8>,
+    ) -> core::result::Result<Self::Value, E> {
+        Ok(v)
+    }
+
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(
+        self,
+        mut seq: A,
+    ) -> core::result::Result<Self::Value, A::Error> {
+        let mut res = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+        while let Some(b) = seq.next_element()? {
+            res.push(b);
+        }
+        Ok(res)
+    }
+}
@@ -0,0 +1,253 @@
+//! Constant-time helpers for the arithmetic on secret values.
+//!
+//! Branches and memory accesses only depend on public values, such as loop
+//! bounds and moduli; secret values only go through additions, multiplications,
+//! shifts and masks. Reductions by a public modulus use a Barrett reduction
+//! rather than `%`, which compiles to a `div` instruction whose timing depends
+//! on its operands, e.g. in unoptimized builds.
+
+/// All ones if `x` is negative, zero otherwise.
+#[inline(always)]
+fn neg_mask(x: i64) -> u64 {
+    (x >> 63) as u64
+}
+
+/// `a` where `mask` is set, `b` elsewhere.
+#[inline(always)]
+fn select(mask: u64, a: u64, b: u64) -> u64 {
+    b ^ ((a ^ b) & mask)
+}
+
+/// `x - d` if `x >= d`, `x` otherwise; requires `x < 2d < 2^63`.
+#[inline(always)]
+pub(crate) fn csub(x: u64, d: u64) -> u64 {
+    let y = x.wrapping_sub(d);
+    y.wrapping_add(d & neg_mask(y as i64))
+}
+
+/// `x mod d` for a public odd modulus `d < 2^62`.
+#[inline(always)]
+pub(crate) fn rem(x: u64, d: u64) -> u64 {
+    // floor(2^64 / d) underestimates 2^64 / d by less than one, so the
+    // quotient is too small by at most one
+    let m = u64::MAX / d;
+    let q = ((x as u128 * m as u128) >> 64) as u64;
+    csub(x - q * d, d)
+}
+
+/// The representative of `x` in `[0, d)` for a public odd modulus `d < 2^62`.
+#[inline(always)]
+pub(crate) fn lift(x: i64, d: u64) -> u64 {
+    let mask = neg_mask(x);
+    let r = rem((x as u64 ^ mask).wrapping_sub(mask), d);
+    select(mask, csub(d - r, d), r)
+}
+
+/// Sort `keys` in increasing order with a bitonic network, whose sequence of
+/// comparisons is the same for all keys. The length must be a power of two,
+/// and the keys smaller than `2^63`.
+pub(crate) fn sort(keys: &mut [u64]) {
+    let n = keys.len();
+    debug_assert!(n.is_power_of_two());
+    let mut k = 2;
+    while k <= n {
+        let mut j = k >> 1;
+        while j > 0 {
+            for i in 0..n {
+                let l = i ^ j;
+                if l > i {
+                    if i & k == 0 {
+                        minmax(keys, i, l)
+                    } else {
+                        minmax(keys, l, i)
+                    }
+                }
+            }
+            j >>= 1;
+        }
+        k <<= 1;
+    }
+}
+
+/// Put the smaller of `keys[i]` and `keys[j]` at `i` and the larger at `j`.
+#[inline(always)]
+fn minmax(keys: &mut [u64], i: usize, j: usize) {
+    let (a, b) = (keys[i], keys[j]);
+    let swap = (a ^ b) & neg_mask(b.wrapping_sub(a) as i64);
+    keys[i] = a ^ swap;
+    keys[j] = b ^ swap;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::hots::{HomomorphicOneTimeSignature, HotsSK, HOTS};
+    use crate::{SignedPoly, BETA_S, LARGE_MODULUS, SMALL_MODULUS};
+    use rand::{Rng, RngCore, SeedableRng};
+    use rand_chacha::ChaCha20Rng;
+    use std::hint::black_box;
+    use std::time::Instant;
+
+    #[test]
+    fn test_reductions() {
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+        for d in [
+            SMALL_MODULUS as u64,
+            LARGE_MODULUS as u64,
+            2 * BETA_S as u64 + 1,
+        ] {
+            let edges = [0, 1, d - 1, d, d + 1, 2 * d - 1, u64::MAX, i64::MAX as u64];
+            let samples = edges
+                .into_iter()
+                .chain((0..2000).map(|i| rng.next_u64() >> (i % 2 * 20)));
+            for x in samples {
+                assert_eq!(rem(x, d), x % d);
+                assert_eq!(lift(x as i64, d), (x as i64).rem_euclid(d as i64) as u64);
+            }
+            for x in 0..2 * d.min(1 << 16) {
+                assert_eq!(csub(x, d), x % d);
+            }
+            assert_eq!(lift(i64::MIN, d), i64::MIN.rem_euclid(d as i64) as u64);
+        }
+    }
+
+    #[test]
+    fn test_sort() {
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+        for n in [1, 2, 8, 512] {
+            let mut keys: Vec<u64> = (0..n).map(|_| rng.next_u64() >> 1).collect();
+            // with repeated keys
+            keys[n / 2] = keys[0];
+            let mut expected = keys.clone();
+            expected.sort_unstable();
+            sort(&mut keys);
+            assert_eq!(keys, expected);
+        }
+    }
+
+    /// Welch's t statistic of two samples.
+    fn welch_t(a: &[f64], b: &[f64]) -> f64 {
+        let moments = |x: &[f64]| {
+            let n = x.len() as f64;
+            let mean = x.iter().sum::<f64>() / n;
+            let var = x.iter().map(|e| (e - mean) * (e - mean)).sum::<f64>() / (n - 1.0);
+            (mean, var / n)
+        };
+        let (mean_a, var_a) = moments(a);
+        let (mean_b, var_b) = moments(b);
+        (mean_a - mean_b) / (var_a + var_b).sqrt()
+    }
+
+    /// A dudect-style timing test: `run` is timed on inputs of two classes,
+    /// given by `input`, in a random order. The measurements above a
+    /// percentile are cropped to drop interruptions, and the timings of the
+    /// classes are compared with Welch's t-test. Returns the largest `|t|`
+    /// over the crops; values above 10 indicate a timing leak.
+    fn dudect<I>(
+        num_samples: usize,
+        mut input: impl FnMut(bool, &mut ChaCha20Rng) -> I,
+        mut run: impl FnMut(&I),
+    ) -> f64 {
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+        let mut timings = Vec::with_capacity(num_samples);
+        for _ in 0..num_samples {
+            let class = rng.gen::<bool>();
+            let input = input(class, &mut rng);
+            let start = Instant::now();
+            run(black_box(&input));
+            timings.push((class, start.elapsed().as_nanos() as f64));
+        }
+
+        let mut sorted: Vec<f64> = timings.iter().map(|(_, t)| *t).collect();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        [0.5, 0.75, 0.9, 1.0]
+            .into_iter()
+            .map(|percentile| {
+                let threshold = sorted[((num_samples - 1) as f64 * percentile) as usize];
+                let class = |c: bool| -> Vec<f64> {
+                    timings
+                        .iter()
+                        .filter(|(class, t)| *class == c && *t <= threshold)
+                        .map(|(_, t)| *t)
+                        .collect()
+                };
+                welch_t(&class(false), &class(true)).abs()
+            })
+            .fold(0.0, f64::max)
+    }
+
+    const T_THRESHOLD: f64 = 10.0;
+
+    #[test]
+    fn test_welch_t() {
+        let a: Vec<f64> = (0..100).map(|i| (i % 10) as f64).collect();
+        assert_eq!(welch_t(&a, &a), 0.0);
+        let b: Vec<f64> = a.iter().map(|x| x + 1.0).collect();
+        // the means differ by 1 and each variance over n is 1/12
+        assert!((welch_t(&a, &b) + 6f64.sqrt()).abs() < 1e-9);
+    }
+
+    /// An rng with a fixed seed for the first class, and a random one for the
+    /// second.
+    fn class_rng(class: bool, rng: &mut ChaCha20Rng) -> ChaCha20Rng {
+        let seed = if class { rng.gen() } else { [1u8; 32] };
+        ChaCha20Rng::from_seed(seed)
+    }
+
+    // The timing tests are only meaningful in release builds on a quiet
+    // machine: `cargo test --release -- --ignored dudect`.
+
+    #[test]
+    #[ignore]
+    fn dudect_fixed_weight_ternary() {
+        let t = dudect(20_000, class_rng, |rng: &ChaCha20Rng| {
+            black_box(SignedPoly::rand_fixed_weight_ternary(
+                &mut rng.clone(),
+                BETA_S,
+            ));
+        });
+        assert!(t < T_THRESHOLD, "t = {}", t);
+    }
+
+    #[test]
+    #[ignore]
+    fn dudect_mod_beta_s() {
+        let t = dudect(20_000, class_rng, |rng: &ChaCha20Rng| {
+            black_box(SignedPoly::rand_mod_beta_s(&mut rng.clone()));
+        });
+        assert!(t < T_THRESHOLD, "t = {}", t);
+    }
+
+    #[test]
+    #[ignore]
+    fn dudect_derive_sk() {
+        let t = dudect(
+            2_000,
+            |class, rng| if class { rng.gen() } else { [1u8; 32] },
+            |seed: &[u8; 32]| {
+                black_box(HOTS::derive_sk(seed, 0));
+            },
+        );
+        assert!(t < T_THRESHOLD, "t = {}", t);
+    }
+
+    #[test]
+    #[ignore]
+    fn dudect_hots_sign() {
+        let keys: Vec<HotsSK> = (0..16).map(|i| HOTS::derive_sk(&[i; 32], 0)).collect();
+        let t = dudect(
+            20_000,
+            |class, rng| {
+                if class {
+                    rng.gen_range(1..keys.len())
+                } else {
+                    0
+                }
+            },
+            |&i: &usize| {
+                black_box(HOTS::sign(&keys[i], b"message"));
+            },
+        );
+        assert!(t < T_THRESHOLD, "t = {}", t);
+    }
+}
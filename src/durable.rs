@@ -0,0 +1,20 @@
+//! Helpers to make file updates survive a crash.
+
+use crate::error::Result;
+use std::{fs::File, path::Path};
+
+/// Persist the creation or renaming of the file at `path` in its directory.
+pub(crate) fn sync_parent(path: &Path) -> Result<()> {
+    #[cfg(unix)]
+    if let Some(dir) = path.parent() {
+        let dir = if dir.as_os_str().is_empty() {
+            Path::new(".")
+        } else {
+            dir
+        };
+        File::open(dir)?.sync_all()?;
+    }
+    #[cfg(not(unix))]
+    let _ = path;
+    Ok(())
+}
@@ -0,0 +1,123 @@
+use core::fmt;
+
+/// Errors returned by this crate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The input ended before the object was fully decoded.
+    UnexpectedEnd,
+    /// The input has bytes left after the object was decoded.
+    TrailingBytes(usize),
+    /// The encoding version is not supported.
+    UnsupportedVersion(u8),
+    /// A flag byte is neither 0 nor 1.
+    InvalidFlag(u8),
+    /// A coefficient is out of the range allowed for its type.
+    InvalidCoefficient(i64),
+    /// A path does not have `height - 1` levels.
+    InvalidPathLength(usize),
+    /// A leaf index is out of `0..1 << (height - 1)`.
+    InvalidIndex(usize),
+    /// A tree height is out of `MIN_HEIGHT..=MAX_HEIGHT`.
+    InvalidHeight(usize),
+    /// The number of tree nodes does not match the tree height.
+    InvalidTreeSize(usize),
+    /// The components of a signature disagree on whether they are randomized.
+    InconsistentRandomization,
+    /// The padding bits of a compact encoding are not zero.
+    InvalidPadding,
+    /// The one-time key at this index has already been used.
+    IndexReused(usize),
+    /// The one-time key at this index is reserved for the proof of possession.
+    ReservedIndex(usize),
+    /// The signer state is in use by another signer.
+    StateLocked,
+    /// The signer state or tree file belongs to a different key.
+    KeyMismatch,
+    /// Two inputs that must have the same length do not; `(expected, found)`.
+    LengthMismatch(usize, usize),
+    /// At least one input is required.
+    EmptyInput,
+    /// Objects for different leaf indices cannot be aggregated; `(expected, found)`.
+    MixedIndices(usize, usize),
+    /// The object has already been randomized.
+    AlreadyRandomized,
+    /// Only randomized objects can be aggregated.
+    NotRandomized,
+    /// A file does not start with the expected magic bytes.
+    InvalidMagic,
+    /// The object was created under different public parameters.
+    ParameterMismatch,
+    /// A signer position is outside of the committee.
+    UnknownSigner(usize),
+    /// The signer at this position has already been included.
+    DuplicateSigner(usize),
+    /// The signature of the signer at this position does not verify.
+    InvalidSignature(usize),
+    /// A slot duration must not be zero.
+    InvalidSlotDuration,
+    /// The first slot of the schedule has not started yet.
+    BeforeGenesis,
+    /// The slot has already ended.
+    SlotExpired(usize),
+    /// A tree traversal state does not match its leaf index.
+    InconsistentState,
+    /// An I/O error occurred while reading or writing a file.
+    Io(std::io::ErrorKind),
+}
+
+pub type Result<T> = core::result::Result<T, Error>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::UnexpectedEnd => write!(f, "unexpected end of input"),
+            Error::TrailingBytes(len) => write!(f, "{} trailing bytes after decoding", len),
+            Error::UnsupportedVersion(v) => write!(f, "unsupported encoding version {}", v),
+            Error::InvalidFlag(b) => write!(f, "invalid flag byte {:#04x}", b),
+            Error::InvalidCoefficient(c) => write!(f, "coefficient {} out of range", c),
+            Error::InvalidPathLength(len) => write!(f, "invalid path length {}", len),
+            Error::InvalidIndex(index) => write!(f, "invalid leaf index {}", index),
+            Error::InvalidHeight(height) => write!(f, "invalid tree height {}", height),
+            Error::InvalidTreeSize(len) => write!(f, "invalid number of tree nodes {}", len),
+            Error::InconsistentRandomization => {
+                write!(f, "signature components are inconsistently randomized")
+            }
+            Error::InvalidPadding => write!(f, "non-zero padding bits"),
+            Error::IndexReused(index) => write!(f, "index {} has already been used", index),
+            Error::ReservedIndex(index) => {
+                write!(f, "index {} is reserved for the proof of possession", index)
+            }
+            Error::StateLocked => write!(f, "state is in use by another signer"),
+            Error::KeyMismatch => write!(f, "state belongs to a different key"),
+            Error::LengthMismatch(expected, found) => {
+                write!(f, "length mismatch: expected {}, found {}", expected, found)
+            }
+            Error::EmptyInput => write!(f, "empty input"),
+            Error::MixedIndices(expected, found) => {
+                write!(f, "mixed indices: expected {}, found {}", expected, found)
+            }
+            Error::AlreadyRandomized => write!(f, "already randomized"),
+            Error::NotRandomized => write!(f, "not randomized"),
+            Error::InvalidMagic => write!(f, "invalid magic bytes"),
+            Error::ParameterMismatch => write!(f, "created under different parameters"),
+            Error::UnknownSigner(signer) => write!(f, "signer {} is not in the committee", signer),
+            Error::DuplicateSigner(signer) => write!(f, "signer {} is already included", signer),
+            Error::InvalidSignature(signer) => {
+                write!(f, "the signature of signer {} does not verify", signer)
+            }
+            Error::InvalidSlotDuration => write!(f, "the slot duration is zero"),
+            Error::BeforeGenesis => write!(f, "the schedule has not started yet"),
+            Error::SlotExpired(slot) => write!(f, "slot {} has already ended", slot),
+            Error::InconsistentState => write!(f, "inconsistent traversal state"),
+            Error::Io(kind) => write!(f, "I/O error: {}", kind),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::Io(e.kind())
+    }
+}
@@ -1,5 +1,8 @@
+use crate::codec::{Decode, Encode, Reader};
+use crate::error::Result;
 use crate::param::LARGE_MODULUS_BITS;
-use crate::poly::{LargePoly, SmallNTTPoly, SmallPoly};
+use crate::poly::{fixed_base_sum, HashMode, LargePoly, SmallNTTPoly, SmallPoly};
+use crate::xof::XofRng;
 use crate::SignedPoly;
 
 use rand::Rng;
@@ -8,19 +11,27 @@ use rayon::iter::IntoParallelIterator;
 #[cfg(feature = "parallel")]
 use rayon::iter::ParallelIterator;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone)]
 pub struct HOTSHash {
     pub(crate) param_h: [SmallNTTPoly; LARGE_MODULUS_BITS << 1],
+    mode: HashMode,
 }
 
 impl Default for HOTSHash {
     fn default() -> Self {
         Self {
             param_h: [SmallNTTPoly::default(); LARGE_MODULUS_BITS << 1],
+            mode: HashMode::default(),
         }
     }
 }
 
+impl PartialEq for HOTSHash {
+    fn eq(&self, other: &Self) -> bool {
+        self.param_h == other.param_h
+    }
+}
+
 impl HOTSHash {
     pub fn init<R: Rng>(rng: &mut R) -> Self {
         let mut res = Self::default();
@@ -32,14 +43,27 @@ impl HOTSHash {
         res
     }
 
+    /// Derive the parameters from a public seed.
+    pub fn from_seed(seed: &[u8]) -> Self {
+        let mut res = Self::default();
+
+        for (i, e) in res.param_h.iter_mut().enumerate() {
+            let tmp = SmallPoly::rand_poly(&mut XofRng::new(seed, b"hots hash", i));
+            *e = (&tmp).into();
+        }
+        res
+    }
+
     /// Hash function.
-    /// Cost: 2*LARGE_MODULUS_BITS NTT and 1 INV_NTT.
+    /// Cost: 2*LARGE_MODULUS_BITS NTT and 1 INV_NTT, or less for binary inputs
+    /// in [`HashMode::FixedBase`].
     pub fn hash(&self, inputs: &[SignedPoly]) -> SmallPoly {
-        // TODO: check the cost for fixed bases
-        // may be faster than NTT
-
         assert_eq!(inputs.len(), LARGE_MODULUS_BITS << 1);
 
+        if self.mode == HashMode::FixedBase {
+            return (&self.fixed_base_sum(inputs)).into();
+        }
+
         let mut res = SmallNTTPoly::default();
 
         #[cfg(feature = "parallel")]
@@ -69,6 +93,30 @@ impl HOTSHash {
         (&res).into()
     }
 
+    /// The sum of the products with the bases in [`HashMode::FixedBase`].
+    fn fixed_base_sum(&self, inputs: &[SignedPoly]) -> SmallNTTPoly {
+        let ntt = |x: &SignedPoly| SmallNTTPoly::from_binary(&x.coeffs).unwrap_or_else(|| x.into());
+
+        #[cfg(feature = "parallel")]
+        let inputs: Vec<SmallNTTPoly> = inputs.into_par_iter().map(ntt).collect();
+
+        #[cfg(not(feature = "parallel"))]
+        let inputs = inputs.iter().map(ntt);
+
+        fixed_base_sum(&self.param_h, inputs)
+    }
+
+    /// Select how the hash is evaluated; the digests do not depend on it.
+    pub fn with_mode(mut self, mode: HashMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// How the hash is evaluated.
+    pub fn mode(&self) -> HashMode {
+        self.mode
+    }
+
     pub(crate) fn decom_then_hash(&self, first: &LargePoly, second: &LargePoly) -> SmallPoly {
         self.hash_separate_inputs(&first.decompose(), &second.decompose())
     }
@@ -83,6 +131,18 @@ impl HOTSHash {
     }
 }
 
+impl Encode for HOTSHash {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.param_h.encode(out)
+    }
+}
+
+impl Decode for HOTSHash {
+    fn decode_into(&mut self, reader: &mut Reader) -> Result<()> {
+        self.param_h.decode_into(reader)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -102,6 +162,34 @@ mod test {
         let _ = hasher.hash(&inputs);
     }
 
+    #[test]
+    fn test_fixed_base() {
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+        let hasher = HOTSHash::init(&mut rng);
+        let fixed_base = hasher.clone().with_mode(HashMode::FixedBase);
+        assert_eq!(fixed_base, hasher);
+
+        for _ in 0..5 {
+            let first = LargePoly::rand_poly(&mut rng);
+            let second = LargePoly::rand_poly(&mut rng);
+            assert_eq!(
+                fixed_base.decom_then_hash(&first, &second),
+                hasher.decom_then_hash(&first, &second)
+            );
+
+            let r: TerPolyCoeffEncoding = (&SignedPoly::rand_ternary(&mut rng, 10)).into();
+            let mut randomized = first.decompose();
+            for x in randomized.iter_mut().step_by(2) {
+                *x = SignedPoly::ter_mul_bin(&r, x);
+            }
+            let second = second.decompose();
+            assert_eq!(
+                fixed_base.hash_separate_inputs(&randomized, &second),
+                hasher.hash_separate_inputs(&randomized, &second)
+            );
+        }
+    }
+
     #[test]
     fn test_homomorphism() {
         let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
@@ -2,17 +2,24 @@ mod hash;
 mod pk;
 mod sig;
 
+use crate::beta_sigma_agg;
+use crate::codec::{Decode, Encode, Reader};
+use crate::error::{Error, Result};
 use crate::poly::LargeNTTPoly;
 use crate::poly::LargePoly;
 use crate::poly::SmallPoly;
 use crate::randomizer::Randomizers;
+use crate::xof::XofRng;
 use crate::SignedPoly;
 use crate::BETA_S;
+use crate::BETA_SIGMA;
 use crate::GAMMA;
 use rand::Rng;
 use rand::SeedableRng;
 use rand_chacha::ChaCha20Rng;
 use sha2::Digest;
+use std::fmt;
+use zeroize::Zeroize;
 
 pub use hash::HOTSHash;
 pub use pk::HotsPK;
@@ -22,16 +29,77 @@ pub use sig::HotsSig;
 pub struct HOTS;
 
 // HOTS public parameters
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct HotsParam {
     pub(crate) a: [LargeNTTPoly; GAMMA],
 }
 
-// HOTS secret key
-#[derive(Debug, Clone, Copy)]
+impl Default for HotsParam {
+    fn default() -> Self {
+        Self {
+            a: [LargeNTTPoly::default(); GAMMA],
+        }
+    }
+}
+
+impl HotsParam {
+    /// Derive the parameters from a public seed.
+    pub fn from_seed(seed: &[u8]) -> Self {
+        let mut a = [LargeNTTPoly::default(); GAMMA];
+        a.iter_mut().enumerate().for_each(|(i, x)| {
+            *x = LargeNTTPoly::from(&LargePoly::rand_poly(&mut XofRng::new(seed, b"hots a", i)))
+        });
+
+        Self { a }
+    }
+}
+
+// HOTS secret key, wiped on drop; kept on the heap so that it is not
+// copied around when moved
+#[derive(Clone)]
 pub struct HotsSK {
-    pub(crate) s0: [LargeNTTPoly; GAMMA],
-    pub(crate) s1: [LargeNTTPoly; GAMMA],
+    pub(crate) s0: Box<[LargeNTTPoly; GAMMA]>,
+    pub(crate) s1: Box<[LargeNTTPoly; GAMMA]>,
+}
+
+impl Zeroize for HotsSK {
+    fn zeroize(&mut self) {
+        self.s0
+            .iter_mut()
+            .chain(self.s1.iter_mut())
+            .for_each(|x| x.coeffs.zeroize());
+    }
+}
+
+impl Drop for HotsSK {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl fmt::Debug for HotsSK {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("HotsSK").finish_non_exhaustive()
+    }
+}
+
+/// Overwrite the state of a generator that was seeded with secret data.
+fn clear_rng(rng: &mut ChaCha20Rng) {
+    // a volatile write, as in `zeroize`, so that it is not optimized away
+    unsafe { std::ptr::write_volatile(rng, ChaCha20Rng::from_seed([0u8; 32])) };
+    std::sync::atomic::compiler_fence(std::sync::atomic::Ordering::SeqCst);
+}
+
+impl Encode for HotsParam {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.a.encode(out)
+    }
+}
+
+impl Decode for HotsParam {
+    fn decode_into(&mut self, reader: &mut Reader) -> Result<()> {
+        self.a.decode_into(reader)
+    }
 }
 
 pub trait HomomorphicOneTimeSignature {
@@ -50,7 +118,7 @@ pub trait HomomorphicOneTimeSignature {
 
     fn verify(pk: &Self::PK, message: &[u8], sig: &Self::Signature, pp: &Self::Param) -> bool;
 
-    fn aggregate(sigs: &[Self::Signature], roots: &[SmallPoly]) -> Self::Signature;
+    fn aggregate(sigs: &[Self::Signature], roots: &[SmallPoly]) -> Result<Self::Signature>;
 
     fn batch_verify(
         pks: &[Self::PK],
@@ -77,40 +145,56 @@ impl HomomorphicOneTimeSignature for HOTS {
 
     fn derive_sk(seed: &[u8; 32], counter: usize) -> Self::SK {
         // initialize the rng with seed and counter
-        let seed = [seed.as_ref(), counter.to_be_bytes().as_ref()].concat();
+        let mut input = [seed.as_ref(), counter.to_be_bytes().as_ref()].concat();
         let mut hasher = sha2::Sha256::new();
-        hasher.update(seed);
-        let seed = hasher.finalize().into();
+        hasher.update(&input);
+        input.zeroize();
+        let mut seed: [u8; 32] = hasher.finalize().into();
         let mut rng = ChaCha20Rng::from_seed(seed);
-
-        // sample the secret key
-        let mut s0 = [LargeNTTPoly::default(); GAMMA];
-        let mut s1 = [LargeNTTPoly::default(); GAMMA];
-
-        s0.iter_mut()
-            .for_each(|x| *x = LargeNTTPoly::from(&SignedPoly::rand_mod_beta_s(&mut rng)));
-        s1.iter_mut().for_each(|x| {
-            *x = LargeNTTPoly::from(&SignedPoly::rand_fixed_weight_ternary(&mut rng, BETA_S))
-        });
-        Self::SK { s0, s1 }
+        seed.zeroize();
+
+        // sample the secret key, wiping the coefficients before the NTT
+        let mut sk = Self::SK {
+            s0: Box::new([LargeNTTPoly::default(); GAMMA]),
+            s1: Box::new([LargeNTTPoly::default(); GAMMA]),
+        };
+        let mut poly = SignedPoly::default();
+        for x in sk.s0.iter_mut() {
+            poly = SignedPoly::rand_mod_beta_s(&mut rng);
+            *x = LargeNTTPoly::from(&poly);
+        }
+        for x in sk.s1.iter_mut() {
+            poly = SignedPoly::rand_fixed_weight_ternary(&mut rng, BETA_S);
+            *x = LargeNTTPoly::from(&poly);
+        }
+        poly.coeffs.zeroize();
+        clear_rng(&mut rng);
+        sk
     }
 
     fn key_gen(seed: &[u8; 32], counter: usize, pp: &Self::Param) -> (Self::PK, Self::SK) {
         let sk = Self::derive_sk(seed, counter);
 
-        // build the pk
-        let mut pk = HotsPK::default();
-
+        // build the pk, summing in the NTT domain so that only the sums, which
+        // are public, are converted back
+        let mut v0 = LargeNTTPoly::default();
+        let mut v1 = LargeNTTPoly::default();
         pp.a.iter()
             .zip(sk.s0.iter().zip(sk.s1.iter()))
             .for_each(|(&a, (&s0, &s1))| {
-                pk.v0 += (&(a * s0)).into();
-                pk.v1 += (&(a * s1)).into();
+                v0 += a * s0;
+                v1 += a * s1;
             });
+        let pk = HotsPK {
+            v0: (&v0).into(),
+            v1: (&v1).into(),
+        };
 
         (pk, sk)
     }
 
+    // constant time in `sk`: the arithmetic in the NTT domain does not branch
+    // on the coefficients, and only the signature is converted back
     fn sign(sk: &Self::SK, message: &[u8]) -> Self::Signature {
         let mut sigma = [LargePoly::default(); GAMMA];
         let hm: LargeNTTPoly = (&SignedPoly::from_hash_message(message)).into();
@@ -124,19 +208,14 @@ impl HomomorphicOneTimeSignature for HOTS {
     }
 
     fn verify(pk: &Self::PK, message: &[u8], sig: &Self::Signature, pp: &Self::Param) -> bool {
-        //todo: check norm of signature
-        let hm: LargeNTTPoly = (&SignedPoly::from_hash_message(message)).into();
-        let mut left = LargeNTTPoly::default();
-        for (&a, s) in pp.a.iter().zip(sig.sigma.iter()) {
-            left += a * LargeNTTPoly::from(s)
-        }
-        let right = hm * LargeNTTPoly::from(&pk.v0) + LargeNTTPoly::from(&pk.v1);
-        left == right
+        verify_with_norm_bound(pk, message, sig, BETA_SIGMA, pp)
     }
 
-    fn aggregate(sigs: &[Self::Signature], roots: &[SmallPoly]) -> Self::Signature {
+    fn aggregate(sigs: &[Self::Signature], roots: &[SmallPoly]) -> Result<Self::Signature> {
         // check that length are correct
-        assert_eq!(sigs.len(), roots.len());
+        if sigs.len() != roots.len() {
+            return Err(Error::LengthMismatch(roots.len(), sigs.len()));
+        }
 
         let randomizers = Randomizers::from_pks(roots);
         Self::Signature::aggregate_with_randomizers(sigs, &randomizers)
@@ -149,22 +228,48 @@ impl HomomorphicOneTimeSignature for HOTS {
         roots: &[SmallPoly],
         pp: &Self::Param,
     ) -> bool {
-        // check that length are correct
-        assert_eq!(pks.len(), roots.len());
+        let agg_pk = match Self::PK::aggregate(pks, roots) {
+            Ok(agg_pk) => agg_pk,
+            Err(_) => return false,
+        };
+        verify_with_norm_bound(&agg_pk, message, sig, beta_sigma_agg(pks.len()), pp)
+    }
+}
 
-        let agg_pk = Self::PK::aggregate(pks, roots);
-        Self::verify(&agg_pk, message, sig, pp)
+/// Verify a (possibly aggregated) signature, rejecting signatures whose
+/// coefficients exceed `norm_bound`.
+pub(crate) fn verify_with_norm_bound(
+    pk: &HotsPK,
+    message: &[u8],
+    sig: &HotsSig,
+    norm_bound: u32,
+    pp: &HotsParam,
+) -> bool {
+    if sig.infinity_norm() > norm_bound {
+        return false;
+    }
+
+    let hm: LargeNTTPoly = (&SignedPoly::from_hash_message(message)).into();
+    let mut left = LargeNTTPoly::default();
+    for (&a, s) in pp.a.iter().zip(sig.sigma.iter()) {
+        left += a * LargeNTTPoly::from(s)
     }
+    let right = hm * LargeNTTPoly::from(&pk.v0) + LargeNTTPoly::from(&pk.v1);
+    left == right
 }
 
 pub(crate) fn batch_verify_with_aggregated_pk(
     agg_pk: &RandomizedHOTSPK,
     message: &[u8],
     agg_sig: &HotsSig,
+    num_signers: usize,
     pp: &HotsParam,
 ) -> bool {
+    if num_signers == 0 {
+        return false;
+    }
     let agg_pk: HotsPK = agg_pk.into();
-    HOTS::verify(&agg_pk, message, agg_sig, pp)
+    verify_with_norm_bound(&agg_pk, message, agg_sig, beta_sigma_agg(num_signers), pp)
 }
 
 #[cfg(test)]
@@ -193,7 +298,7 @@ mod test {
                 sigs.push(sig);
                 roots.push(SmallPoly::rand_poly(&mut rng));
             }
-            let agg_sig = HOTS::aggregate(&sigs, &roots);
+            let agg_sig = HOTS::aggregate(&sigs, &roots).unwrap();
             assert!(HOTS::batch_verify(
                 &pks,
                 message.as_ref(),
@@ -202,8 +307,97 @@ mod test {
                 &pp
             ));
             let pk_randomized: Vec<RandomizedHOTSPK> = pks.iter().map(|x| x.into()).collect();
-            let agg_pk_randomized = RandomizedHOTSPK::aggregate(&pk_randomized, &roots);
-            batch_verify_with_aggregated_pk(&agg_pk_randomized, message.as_ref(), &agg_sig, &pp);
+            let agg_pk_randomized = RandomizedHOTSPK::aggregate(&pk_randomized, &roots).unwrap();
+            batch_verify_with_aggregated_pk(
+                &agg_pk_randomized,
+                message.as_ref(),
+                &agg_sig,
+                pks.len(),
+                &pp,
+            );
         }
     }
+
+    #[test]
+    fn test_hots_norm_bound() {
+        let message = "this is the message to sign";
+        let seed = [0u8; 32];
+        let mut rng = ChaCha20Rng::from_seed(seed);
+        let pp = HOTS::setup(&mut rng);
+
+        for counter in 0..10 {
+            let (pk, sk) = HOTS::key_gen(&seed, counter, &pp);
+            let sig = HOTS::sign(&sk, message.as_ref());
+            let norm = sig.infinity_norm();
+            assert!(norm <= BETA_SIGMA);
+            assert!(verify_with_norm_bound(
+                &pk,
+                message.as_ref(),
+                &sig,
+                norm,
+                &pp
+            ));
+            assert!(!verify_with_norm_bound(
+                &pk,
+                message.as_ref(),
+                &sig,
+                norm - 1,
+                &pp
+            ));
+        }
+    }
+
+    #[test]
+    fn test_aggregation_errors() {
+        let message = "this is the message to sign";
+        let seed = [0u8; 32];
+        let mut rng = ChaCha20Rng::from_seed(seed);
+        let pp = HOTS::setup(&mut rng);
+
+        let (pk, sk) = HOTS::key_gen(&seed, 0, &pp);
+        let sig = HOTS::sign(&sk, message.as_ref());
+        let roots = [
+            SmallPoly::rand_poly(&mut rng),
+            SmallPoly::rand_poly(&mut rng),
+        ];
+
+        assert_eq!(
+            HOTS::aggregate(&[sig], &roots).unwrap_err(),
+            Error::LengthMismatch(2, 1)
+        );
+        assert_eq!(HOTS::aggregate(&[], &[]).unwrap_err(), Error::EmptyInput);
+        assert!(!HOTS::batch_verify(
+            &[pk],
+            message.as_ref(),
+            &sig,
+            &roots,
+            &pp
+        ));
+
+        let agg_sig = HOTS::aggregate(&[sig], &roots[..1]).unwrap();
+        assert_eq!(
+            HOTS::aggregate(&[agg_sig], &roots[..1]).unwrap_err(),
+            Error::AlreadyRandomized
+        );
+        assert_eq!(
+            HotsSig::aggregate_randomized_signatures(&[sig]).unwrap_err(),
+            Error::NotRandomized
+        );
+    }
+
+    #[test]
+    fn test_secret_key_wiped() {
+        let seed = [1u8; 32];
+        let mut sk = HOTS::derive_sk(&seed, 0);
+        assert_eq!(format!("{:?}", sk), "HotsSK { .. }");
+        // deterministic, and the rng is cleared without changing the output
+        assert_eq!(HOTS::derive_sk(&seed, 0).s0, sk.s0);
+        assert!(sk.s1.iter().any(|x| x.coeffs.iter().any(|&c| c != 0)));
+        sk.zeroize();
+        assert!(sk
+            .s0
+            .iter()
+            .chain(sk.s1.iter())
+            .all(|x| x.coeffs.iter().all(|&c| c == 0)));
+    }
 }
@@ -1,6 +1,8 @@
 use std::ops::AddAssign;
 
 use crate::{
+    codec::{check_decomposed, impl_serialization, Decode, Encode, Reader},
+    error::{Error, Result},
     poly::{LargePoly, SmallPoly, TerPolyCoeffEncoding},
     HOTSHash, Randomizers, SignedPoly, LARGE_MODULUS_BITS,
 };
@@ -20,14 +22,27 @@ impl HotsPK {
     }
 
     /// Aggregate multiple PKs into a single PK
-    pub(crate) fn aggregate(pks: &[Self], roots: &[SmallPoly]) -> Self {
+    pub(crate) fn aggregate(pks: &[Self], roots: &[SmallPoly]) -> Result<Self> {
+        if pks.len() != roots.len() {
+            return Err(Error::LengthMismatch(roots.len(), pks.len()));
+        }
         // get and apply the randomizers
         let randomizers = Randomizers::from_pks(roots);
         Self::aggregate_with_randomizers(pks, &randomizers)
     }
 
     /// Aggregate a set of pks with randomizes
-    pub(crate) fn aggregate_with_randomizers(pks: &[Self], randomizers: &Randomizers) -> Self {
+    pub(crate) fn aggregate_with_randomizers(
+        pks: &[Self],
+        randomizers: &Randomizers,
+    ) -> Result<Self> {
+        if pks.is_empty() {
+            return Err(Error::EmptyInput);
+        }
+        if pks.len() != randomizers.poly.len() {
+            return Err(Error::LengthMismatch(randomizers.poly.len(), pks.len()));
+        }
+
         let mut pk_and_randomizer: Vec<(Self, LargePoly)> = pks
             .iter()
             .zip(randomizers.poly.iter())
@@ -52,7 +67,7 @@ impl HotsPK {
             agg_pk.v0 += pk.v0;
             agg_pk.v1 += pk.v1;
         }
-        agg_pk
+        Ok(agg_pk)
     }
 }
 
@@ -84,9 +99,9 @@ impl From<&RandomizedHOTSPK> for HotsPK {
 }
 
 impl RandomizedHOTSPK {
-    pub(crate) fn randomize_with(&mut self, ternary: &SignedPoly) {
+    pub(crate) fn randomize_with(&mut self, ternary: &SignedPoly) -> Result<()> {
         if self.is_randomized {
-            panic!("already randomized")
+            return Err(Error::AlreadyRandomized);
         }
         let ternary_coeffs: TerPolyCoeffEncoding = ternary.into();
 
@@ -109,33 +124,74 @@ impl RandomizedHOTSPK {
         });
 
         self.is_randomized = true;
+        Ok(())
     }
 
     pub(crate) fn digest(&self, hasher: &HOTSHash) -> SmallPoly {
         hasher.hash_separate_inputs(&self.v0, &self.v1)
     }
 
+    /// the largest absolute value of the coefficients
+    pub fn infinity_norm(&self) -> u32 {
+        self.v0
+            .iter()
+            .chain(self.v1.iter())
+            .map(|x| x.infinity_norm())
+            .max()
+            .unwrap_or_default()
+    }
+
     /// Aggregate multiple PKs into a single PK
-    pub(crate) fn aggregate(pks: &[Self], roots: &[SmallPoly]) -> Self {
+    pub(crate) fn aggregate(pks: &[Self], roots: &[SmallPoly]) -> Result<Self> {
+        if pks.len() != roots.len() {
+            return Err(Error::LengthMismatch(roots.len(), pks.len()));
+        }
         // get and apply the randomizers
         let randomizers = Randomizers::from_pks(roots);
         Self::aggregate_with_randomizers(pks, &randomizers)
     }
 
     /// Aggregate a set of pks with randomizes
-    pub(crate) fn aggregate_with_randomizers(pks: &[Self], randomizers: &Randomizers) -> Self {
+    pub(crate) fn aggregate_with_randomizers(
+        pks: &[Self],
+        randomizers: &Randomizers,
+    ) -> Result<Self> {
+        if pks.len() != randomizers.poly.len() {
+            return Err(Error::LengthMismatch(randomizers.poly.len(), pks.len()));
+        }
         let mut randomized_pks: Vec<Self> = pks.to_vec();
         randomized_pks
             .iter_mut()
             .zip(randomizers.poly.iter())
-            .for_each(|(x, r)| x.randomize_with(r));
+            .try_for_each(|(x, r)| x.randomize_with(r))?;
+
+        let (&first, rest) = randomized_pks.split_first().ok_or(Error::EmptyInput)?;
+        let mut res = first;
+        rest.iter().for_each(|x| res += *x);
+        Ok(res)
+    }
+}
 
-        let mut res = randomized_pks[0];
-        randomized_pks.iter().skip(1).for_each(|x| res += *x);
-        res
+impl Encode for RandomizedHOTSPK {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.is_randomized.encode(out);
+        self.v0.encode(out);
+        self.v1.encode(out);
     }
 }
 
+impl Decode for RandomizedHOTSPK {
+    fn decode_into(&mut self, reader: &mut Reader) -> Result<()> {
+        self.is_randomized = reader.read_bool()?;
+        self.v0.decode_into(reader)?;
+        self.v1.decode_into(reader)?;
+        check_decomposed(&self.v0, self.is_randomized)?;
+        check_decomposed(&self.v1, self.is_randomized)
+    }
+}
+
+impl_serialization!(RandomizedHOTSPK);
+
 impl AddAssign for RandomizedHOTSPK {
     // Coefficient wise additions without mod reduction.
     fn add_assign(&mut self, other: Self) {
@@ -183,7 +239,7 @@ mod tests {
                 roots.push(SmallPoly::rand_poly(&mut rng));
             }
             let randomizers = Randomizers::from_pks(&roots);
-            let agg_pk_randomized = RandomizedHOTSPK::aggregate(&pks_randomized, &roots);
+            let agg_pk_randomized = RandomizedHOTSPK::aggregate(&pks_randomized, &roots).unwrap();
             let agg_digest = agg_pk_randomized.digest(&hasher);
             let mut agg_digest_rec = SmallPoly::default();
 
@@ -2,7 +2,12 @@
 use rayon::iter::{IntoParallelRefMutIterator, ParallelIterator};
 use std::ops::AddAssign;
 
-use crate::{poly::LargePoly, Randomizers, SignedPoly, GAMMA};
+use crate::{
+    codec::{impl_serialization, Decode, Encode, Reader},
+    error::{Error, Result},
+    poly::LargePoly,
+    Randomizers, SignedPoly, GAMMA,
+};
 
 // HOTS signature
 #[derive(Debug, Clone, Copy)]
@@ -11,11 +16,20 @@ pub struct HotsSig {
     pub(crate) is_randomized: bool,
 }
 
+impl Default for HotsSig {
+    fn default() -> Self {
+        Self {
+            sigma: [LargePoly::default(); GAMMA],
+            is_randomized: false,
+        }
+    }
+}
+
 impl HotsSig {
     /// Randomize an Hots Signature
-    pub fn randomize_with(&mut self, ternary: &SignedPoly) {
+    pub fn randomize_with(&mut self, ternary: &SignedPoly) -> Result<()> {
         if self.is_randomized {
-            panic!("already randomized")
+            return Err(Error::AlreadyRandomized);
         }
 
         let ternary = LargePoly::from(ternary);
@@ -31,19 +45,40 @@ impl HotsSig {
         });
 
         self.is_randomized = true;
+        Ok(())
+    }
+
+    /// the largest absolute value of the coefficients of sigma
+    pub fn infinity_norm(&self) -> u32 {
+        self.sigma
+            .iter()
+            .map(|x| x.infinity_norm())
+            .max()
+            .unwrap_or_default()
     }
 
     /// aggregated randomized signatures
-    pub(crate) fn aggregate_randomized_signatures(sigs: &[Self]) -> Self {
-        let mut res = sigs[0];
-        for &e in sigs.iter().skip(1) {
+    pub(crate) fn aggregate_randomized_signatures(sigs: &[Self]) -> Result<Self> {
+        let (&first, rest) = sigs.split_first().ok_or(Error::EmptyInput)?;
+        if sigs.iter().any(|x| !x.is_randomized) {
+            return Err(Error::NotRandomized);
+        }
+        let mut res = first;
+        for &e in rest {
             res += e;
         }
-        res
+        Ok(res)
     }
 
-    ///
-    pub(crate) fn aggregate_with_randomizers(sigs: &[Self], randomizers: &Randomizers) -> Self {
+    /// randomize and aggregate signatures
+    pub(crate) fn aggregate_with_randomizers(
+        sigs: &[Self],
+        randomizers: &Randomizers,
+    ) -> Result<Self> {
+        if sigs.len() != randomizers.poly.len() {
+            return Err(Error::LengthMismatch(randomizers.poly.len(), sigs.len()));
+        }
+
         #[cfg(feature = "parallel")]
         {
             let mut sig_and_randomizers: Vec<(Self, SignedPoly)> = sigs
@@ -54,7 +89,7 @@ impl HotsSig {
 
             sig_and_randomizers
                 .iter_mut()
-                .for_each(|(s, randomizer)| s.randomize_with(randomizer));
+                .try_for_each(|(s, randomizer)| s.randomize_with(randomizer))?;
             let sig_randomized: Vec<HotsSig> =
                 sig_and_randomizers.iter().map(|(s, _r)| *s).collect();
             Self::aggregate_randomized_signatures(&sig_randomized)
@@ -66,12 +101,28 @@ impl HotsSig {
             sig_randomized
                 .iter_mut()
                 .zip(randomizers.poly.iter())
-                .for_each(|(x, randomizer)| x.randomize_with(randomizer));
+                .try_for_each(|(x, randomizer)| x.randomize_with(randomizer))?;
             Self::aggregate_randomized_signatures(&sig_randomized)
         }
     }
 }
 
+impl Encode for HotsSig {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.is_randomized.encode(out);
+        self.sigma.encode(out);
+    }
+}
+
+impl Decode for HotsSig {
+    fn decode_into(&mut self, reader: &mut Reader) -> Result<()> {
+        self.is_randomized = reader.read_bool()?;
+        self.sigma.decode_into(reader)
+    }
+}
+
+impl_serialization!(HotsSig);
+
 impl AddAssign for HotsSig {
     // Coefficient wise additions without mod reduction.
     fn add_assign(&mut self, other: Self) {
@@ -1,15 +1,26 @@
+use crate::codec::{Decode, Encode, Reader};
+use crate::error::Result;
 use crate::param::SMALL_MODULUS_BITS;
-use crate::poly::{SmallNTTPoly, SmallPoly};
+use crate::poly::{fixed_base_sum, HashMode, SmallNTTPoly, SmallPoly};
+use crate::xof::XofRng;
 use crate::SignedPoly;
 use rand::Rng;
 #[cfg(feature = "parallel")]
 use rayon::iter::IntoParallelIterator;
 #[cfg(feature = "parallel")]
 use rayon::iter::ParallelIterator;
+use sha2::Digest;
 
-#[derive(Debug, Clone, Default, PartialEq)]
+#[derive(Debug, Clone, Default)]
 pub struct HVCHash {
     pub(crate) param_h: [SmallNTTPoly; 28],
+    mode: HashMode,
+}
+
+impl PartialEq for HVCHash {
+    fn eq(&self, other: &Self) -> bool {
+        self.param_h == other.param_h
+    }
 }
 
 impl HVCHash {
@@ -23,14 +34,27 @@ impl HVCHash {
         res
     }
 
+    /// Derive the parameters from a public seed.
+    pub fn from_seed(seed: &[u8]) -> Self {
+        let mut res = Self::default();
+
+        for (i, e) in res.param_h.iter_mut().enumerate() {
+            let tmp = SmallPoly::rand_poly(&mut XofRng::new(seed, b"hvc hash", i));
+            *e = (&tmp).into();
+        }
+        res
+    }
+
     /// Hash function.
-    /// Cost: 2*SMALL_MODULUS_BITS NTT and 1 INV_NTT.
+    /// Cost: 2*SMALL_MODULUS_BITS NTT and 1 INV_NTT, or less for binary inputs
+    /// in [`HashMode::FixedBase`].
     pub fn hash(&self, inputs: &[SmallPoly]) -> SmallPoly {
-        // TODO: check the cost for fixed bases
-        // may be faster than NTT
-
         assert_eq!(inputs.len(), SMALL_MODULUS_BITS << 1);
 
+        if self.mode == HashMode::FixedBase {
+            return (&self.fixed_base_sum(inputs)).into();
+        }
+
         let mut res = SmallNTTPoly::default();
 
         #[cfg(feature = "parallel")]
@@ -60,6 +84,35 @@ impl HVCHash {
         (&res).into()
     }
 
+    /// The sum of the products with the bases in [`HashMode::FixedBase`].
+    fn fixed_base_sum(&self, inputs: &[SmallPoly]) -> SmallNTTPoly {
+        let ntt = |x: &SmallPoly| SmallNTTPoly::from_binary(&x.coeffs).unwrap_or_else(|| x.into());
+
+        #[cfg(feature = "parallel")]
+        let inputs: Vec<SmallNTTPoly> = inputs.into_par_iter().map(ntt).collect();
+
+        #[cfg(not(feature = "parallel"))]
+        let inputs = inputs.iter().map(ntt);
+
+        fixed_base_sum(&self.param_h, inputs)
+    }
+
+    /// Select how the hash is evaluated; the digests do not depend on it.
+    pub fn with_mode(mut self, mode: HashMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// How the hash is evaluated.
+    pub fn mode(&self) -> HashMode {
+        self.mode
+    }
+
+    /// A digest of the parameters, binding stored objects to them.
+    pub fn fingerprint(&self) -> [u8; 32] {
+        sha2::Sha256::digest(crate::codec::to_bytes(self)).into()
+    }
+
     pub(crate) fn decom_then_hash(&self, left: &SmallPoly, right: &SmallPoly) -> SmallPoly {
         self.hash_separate_inputs(&left.decompose(), &right.decompose())
     }
@@ -78,6 +131,18 @@ impl HVCHash {
     }
 }
 
+impl Encode for HVCHash {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.param_h.encode(out)
+    }
+}
+
+impl Decode for HVCHash {
+    fn decode_into(&mut self, reader: &mut Reader) -> Result<()> {
+        self.param_h.decode_into(reader)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -97,6 +162,36 @@ mod test {
         let _ = hasher.hash(&inputs);
     }
 
+    #[test]
+    fn test_fixed_base() {
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+        let hasher = HVCHash::init(&mut rng);
+        let fixed_base = hasher.clone().with_mode(HashMode::FixedBase);
+        assert_eq!(fixed_base.mode(), HashMode::FixedBase);
+        assert_eq!(fixed_base, hasher);
+
+        for _ in 0..5 {
+            let left = SmallPoly::rand_poly(&mut rng);
+            let right = SmallPoly::rand_poly(&mut rng);
+            assert_eq!(
+                fixed_base.decom_then_hash(&left, &right),
+                hasher.decom_then_hash(&left, &right)
+            );
+
+            // inputs that are not all binary, e.g. randomized ones
+            let r: TerPolyCoeffEncoding = (&SignedPoly::rand_ternary(&mut rng, 10)).into();
+            let mut randomized = left.decompose();
+            for x in randomized.iter_mut().step_by(2) {
+                *x = SignedPoly::ter_mul_bin(&r, x);
+            }
+            let right = right.decompose();
+            assert_eq!(
+                fixed_base.hash_separate_inputs(&randomized, &right),
+                hasher.hash_separate_inputs(&randomized, &right)
+            );
+        }
+    }
+
     #[test]
     fn test_homomorphism() {
         let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
@@ -4,20 +4,22 @@ mod tree;
 pub use hash::HVCHash;
 pub use tree::path::Path;
 pub use tree::randomize_path::RandomizedPath;
+pub use tree::traversal::TreeTraversal;
 pub use tree::Tree;
 
+use crate::error::Result;
 use crate::poly::SmallPoly;
 
-pub trait HomomorphicVectorCommitment {
+pub trait HomomorphicVectorCommitment: Sized {
     type Hasher;
     type Commitment;
     type Node;
     type MembershipProof;
     type AggregatedProof;
 
-    fn commit(hasher: &Self::Hasher, msg: &[Self::Node]) -> Self;
+    fn commit(hasher: &Self::Hasher, msg: &[Self::Node]) -> Result<Self>;
 
-    fn open(&self, index: usize) -> Self::MembershipProof;
+    fn open(&self, index: usize) -> Result<Self::MembershipProof>;
 
     fn verify_single(
         hasher: &Self::Hasher,
@@ -34,7 +36,7 @@ pub trait HomomorphicVectorCommitment {
     fn aggregate(
         proofs: &[Self::MembershipProof],
         roots: &[Self::Commitment],
-    ) -> Self::AggregatedProof;
+    ) -> Result<Self::AggregatedProof>;
 }
 
 #[allow(clippy::upper_case_acronyms)]
@@ -47,11 +49,11 @@ impl HomomorphicVectorCommitment for HVC {
     type MembershipProof = Path;
     type AggregatedProof = RandomizedPath;
 
-    fn commit(hasher: &Self::Hasher, msg: &[Self::Node]) -> Self {
-        Self(Tree::new_with_leaf_nodes(msg, hasher))
+    fn commit(hasher: &Self::Hasher, msg: &[Self::Node]) -> Result<Self> {
+        Ok(Self(Tree::new_with_leaf_nodes(msg, hasher)?))
     }
 
-    fn open(&self, index: usize) -> Self::MembershipProof {
+    fn open(&self, index: usize) -> Result<Self::MembershipProof> {
         self.0.gen_proof(index)
     }
 
@@ -74,7 +76,7 @@ impl HomomorphicVectorCommitment for HVC {
     fn aggregate(
         proofs: &[Self::MembershipProof],
         roots: &[Self::Commitment],
-    ) -> Self::AggregatedProof {
+    ) -> Result<Self::AggregatedProof> {
         Path::aggregation(proofs, roots)
     }
 }
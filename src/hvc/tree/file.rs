@@ -0,0 +1,204 @@
+//! On-disk format of a tree, which is memory-mapped when reopened.
+//!
+//! The file consists of a header followed by the nodes in level order, each
+//! encoded with `N` little-endian u16 coefficients:
+//! - magic bytes `SQHV`
+//! - the encoding version
+//! - the height of the tree
+//! - the fingerprint of the `HVCHash` the tree was built with
+
+use super::{Nodes, Tree, NODE_SIZE};
+use crate::{
+    codec::{Decode, Encode, Reader, ENCODING_VERSION},
+    durable::sync_parent,
+    error::{Error, Result},
+    hvc::hash::HVCHash,
+    param::check_height,
+    poly::SmallPoly,
+};
+use memmap2::Mmap;
+use std::{
+    fs::{self, File},
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+const MAGIC: [u8; 4] = *b"SQHV";
+const HEADER_SIZE: usize = MAGIC.len() + 2 + 32;
+
+/// the encoded nodes of a mapped tree file
+pub(super) fn node_bytes(map: &Mmap) -> &[u8] {
+    &map[HEADER_SIZE..]
+}
+
+/// decode the node at the given position in level order
+pub(super) fn read_node(map: &Mmap, index: usize) -> Result<SmallPoly> {
+    let offset = index * NODE_SIZE;
+    let mut node = SmallPoly::default();
+    node.decode_into(&mut Reader::new(
+        &node_bytes(map)[offset..offset + NODE_SIZE],
+    ))?;
+    Ok(node)
+}
+
+impl Tree {
+    /// Write the tree to a file that can be reopened with [`Tree::open`].
+    /// `hasher` must be the one the tree was built with.
+    pub fn save(&self, path: impl AsRef<Path>, hasher: &HVCHash) -> Result<()> {
+        // an empty tree, e.g. the default one, has no nodes to check
+        check_height(self.height)?;
+        if hasher.decom_then_hash(&self.node(1)?, &self.node(2)?) != self.root() {
+            return Err(Error::ParameterMismatch);
+        }
+        let path = path.as_ref();
+        let mut tmp_name = path.as_os_str().to_owned();
+        tmp_name.push(".tmp");
+        let tmp_path = PathBuf::from(tmp_name);
+
+        let file = File::create(&tmp_path)?;
+        let mut writer = BufWriter::new(file);
+        writer.write_all(&MAGIC)?;
+        writer.write_all(&[ENCODING_VERSION, self.height as u8])?;
+        writer.write_all(&hasher.fingerprint())?;
+        let mut buf = Vec::with_capacity(NODE_SIZE);
+        for index in 0..2 * self.num_leaves() - 1 {
+            buf.clear();
+            self.node(index)?.encode(&mut buf);
+            writer.write_all(&buf)?;
+        }
+        writer
+            .into_inner()
+            .map_err(|e| e.into_error())?
+            .sync_all()?;
+        fs::rename(&tmp_path, path)?;
+        sync_parent(path)
+    }
+
+    /// Open a tree file written by [`Tree::save`]. The nodes are memory-mapped
+    /// rather than loaded, and are only read when a proof needs them.
+    /// Fails with [`Error::ParameterMismatch`] if the tree was built with a
+    /// different `hasher`.
+    ///
+    /// # Safety
+    ///
+    /// The file is mapped into memory, so it must not be truncated or
+    /// rewritten, by this or any other process, while the tree or a clone of
+    /// it is alive; otherwise reading a node is undefined behavior. Files
+    /// written by [`Tree::save`] are replaced by a rename, which leaves the
+    /// mapped file intact.
+    pub fn open(path: impl AsRef<Path>, hasher: &HVCHash) -> Result<Self> {
+        let file = File::open(path)?;
+        // safety: the file is not modified while mapped, see `# Safety` above
+        let map = unsafe { Mmap::map(&file)? };
+
+        let mut reader = Reader::new(&map);
+        if reader.read_array::<4>()? != MAGIC {
+            return Err(Error::InvalidMagic);
+        }
+        let version = reader.read_u8()?;
+        if version != ENCODING_VERSION {
+            return Err(Error::UnsupportedVersion(version));
+        }
+        let height = reader.read_u8()? as usize;
+        check_height(height)?;
+        if reader.read_array::<32>()? != hasher.fingerprint() {
+            return Err(Error::ParameterMismatch);
+        }
+        if reader.remaining() != ((1 << height) - 1) * NODE_SIZE {
+            return Err(Error::InvalidTreeSize(reader.remaining() / NODE_SIZE));
+        }
+
+        let tree = Self {
+            height,
+            nodes: Nodes::Mapped(Arc::new(map)),
+        };
+        // `root` relies on the root being valid
+        tree.node(0)?;
+        Ok(tree)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    #[test]
+    fn test_tree_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tree");
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+        let hasher = HVCHash::init(&mut rng);
+
+        let leafs: Vec<SmallPoly> = (0..8).map(|_| SmallPoly::rand_poly(&mut rng)).collect();
+        let tree = Tree::new_with_leaf_nodes(&leafs, &hasher).unwrap();
+        tree.save(&path, &hasher).unwrap();
+        assert!(!dir.path().join("tree.tmp").exists());
+
+        let mapped = Tree::open(&path, &hasher).unwrap();
+        assert!(mapped.is_mapped());
+        assert_eq!(mapped.height(), tree.height());
+        assert_eq!(mapped.root(), tree.root());
+        for index in 0..leafs.len() {
+            assert_eq!(mapped.gen_proof(index), tree.gen_proof(index));
+        }
+        assert_eq!(
+            mapped.gen_proof(leafs.len()).unwrap_err(),
+            Error::InvalidIndex(leafs.len())
+        );
+        assert_eq!(
+            crate::codec::to_bytes(&mapped),
+            crate::codec::to_bytes(&tree)
+        );
+
+        // a mapped tree can be saved again
+        let copy = dir.path().join("copy");
+        mapped.save(&copy, &hasher).unwrap();
+        assert_eq!(fs::read(&copy).unwrap(), fs::read(&path).unwrap());
+
+        // files of other parameters are refused
+        let other_hasher = HVCHash::init(&mut rng);
+        assert_eq!(
+            Tree::open(&path, &other_hasher).unwrap_err(),
+            Error::ParameterMismatch
+        );
+        assert_eq!(
+            tree.save(dir.path().join("other"), &other_hasher)
+                .unwrap_err(),
+            Error::ParameterMismatch
+        );
+        assert_eq!(
+            Tree::default()
+                .save(dir.path().join("empty"), &hasher)
+                .unwrap_err(),
+            Error::InvalidHeight(0)
+        );
+
+        // corrupted files are refused
+        let bytes = fs::read(&path).unwrap();
+        let corrupt = |f: &dyn Fn(&mut Vec<u8>)| {
+            let mut bytes = bytes.clone();
+            f(&mut bytes);
+            fs::write(&copy, &bytes).unwrap();
+            Tree::open(&copy, &hasher).unwrap_err()
+        };
+        assert_eq!(corrupt(&|b| b[0] = b'X'), Error::InvalidMagic);
+        assert_eq!(corrupt(&|b| b[4] = 0), Error::UnsupportedVersion(0));
+        assert_eq!(corrupt(&|b| b[5] = 1), Error::InvalidHeight(1));
+        assert_eq!(corrupt(&|b| b[5] = 5), Error::InvalidTreeSize(15));
+        assert_eq!(
+            corrupt(&|b| b.truncate(b.len() - 1)),
+            Error::InvalidTreeSize(14)
+        );
+        assert_eq!(
+            corrupt(&|b| b[HEADER_SIZE..HEADER_SIZE + 2].copy_from_slice(&[0xff, 0xff])),
+            Error::InvalidCoefficient(0xffff)
+        );
+        assert_eq!(
+            Tree::open(dir.path().join("missing"), &hasher).unwrap_err(),
+            Error::Io(std::io::ErrorKind::NotFound)
+        );
+    }
+}
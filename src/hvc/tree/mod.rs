@@ -5,131 +5,201 @@
 //!
 //!
 
+mod file;
 pub mod path;
 pub mod randomize_path;
+pub mod traversal;
 
 use super::hash::HVCHash;
-use crate::{poly::SmallPoly, Path, HEIGHT};
+use crate::{
+    codec::{Decode, Encode, Reader},
+    error::{Error, Result},
+    param::check_height,
+    poly::SmallPoly,
+    progress::{no_progress, Counter, ProgressCallback, Stage, CHUNK_SIZE},
+    Path, HEIGHT, N,
+};
 use core::fmt;
-use std::fmt::Display;
+use memmap2::Mmap;
+#[cfg(feature = "parallel")]
+use rayon::{
+    iter::{IndexedParallelIterator, ParallelIterator},
+    slice::ParallelSliceMut,
+};
+use std::{fmt::Display, sync::Arc};
+
+/// each node is encoded with N u16 coefficients
+const NODE_SIZE: usize = 2 * N;
 
 #[derive(Clone, Debug, Default)]
 pub struct Tree {
-    /// stores the non-leaf nodes in level order. The first element is the root node.
-    /// The ith nodes (starting at 1st) children are at indices `2*i`, `2*i+1`
-    non_leaf_nodes: Vec<SmallPoly>,
+    height: usize,
+    /// stores all nodes in level order. The first element is the root node, and
+    /// the leaves come last. The ith nodes (starting at 0) children are at
+    /// indices `2*i+1`, `2*i+2`
+    nodes: Nodes,
+}
+
+#[derive(Clone, Debug)]
+enum Nodes {
+    Memory(Vec<SmallPoly>),
+    /// the nodes of a tree file, see `file.rs`
+    Mapped(Arc<Mmap>),
+}
 
-    /// store the hash of leaf nodes from left to right
-    leaf_nodes: Vec<SmallPoly>,
+impl Default for Nodes {
+    fn default() -> Self {
+        Nodes::Memory(Vec::new())
+    }
 }
 
 impl Display for Tree {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let num_non_leaf_nodes = self.num_leaves().saturating_sub(1);
         writeln!(f, "non leaf nodes:")?;
-        for (i, e) in self.non_leaf_nodes.iter().enumerate() {
-            writeln!(f, "{}: {}", i, e)?;
+        for i in 0..num_non_leaf_nodes {
+            writeln!(f, "{}: {}", i, self.node(i).map_err(|_| fmt::Error)?)?;
         }
         writeln!(f, "leaf nodes:")?;
-        for (i, e) in self.leaf_nodes.iter().enumerate() {
-            writeln!(f, "{}: {}", i, e)?;
+        for i in 0..self.num_leaves() {
+            let node = self.node(num_non_leaf_nodes + i).map_err(|_| fmt::Error)?;
+            writeln!(f, "{}: {}", i, node)?;
         }
         Ok(())
     }
 }
 
 impl Tree {
-    /// create an empty tree
+    /// create an empty tree of the default height
     pub fn init(hasher: &HVCHash) -> Self {
         let leaf_nodes = vec![SmallPoly::default(); 1 << (HEIGHT - 1)];
-        Self::new_with_leaf_nodes(&leaf_nodes, hasher)
+        Self::build(&leaf_nodes, hasher)
+    }
+
+    /// create a new tree with leaf nodes;
+    /// the number of leaves must be a power of two, and determines the height
+    pub fn new_with_leaf_nodes(leaf_nodes: &[SmallPoly], hasher: &HVCHash) -> Result<Self> {
+        Self::new_with_progress(leaf_nodes, hasher, &no_progress)
     }
 
-    /// create a new tree with leaf nodes
-    pub fn new_with_leaf_nodes(leaf_nodes: &[SmallPoly], hasher: &HVCHash) -> Self {
+    /// create a new tree with leaf nodes, as [`Tree::new_with_leaf_nodes`], and
+    /// report the hashed nodes to `progress`
+    pub fn new_with_progress(
+        leaf_nodes: &[SmallPoly],
+        hasher: &HVCHash,
+        progress: ProgressCallback,
+    ) -> Result<Self> {
         let len = leaf_nodes.len();
-        assert_eq!(len, 1 << (HEIGHT - 1), "incorrect leaf size");
+        if !len.is_power_of_two() || check_height(len.trailing_zeros() as usize + 1).is_err() {
+            return Err(Error::InvalidTreeSize(len));
+        }
+        Ok(Self::build_with_progress(leaf_nodes, hasher, progress))
+    }
+
+    /// build the tree; the number of leaves must be a valid power of two
+    pub(crate) fn build(leaf_nodes: &[SmallPoly], hasher: &HVCHash) -> Self {
+        Self::build_with_progress(leaf_nodes, hasher, &no_progress)
+    }
 
-        let mut non_leaf_nodes = vec![SmallPoly::default(); (1 << (HEIGHT - 1)) - 1];
+    pub(crate) fn build_with_progress(
+        leaf_nodes: &[SmallPoly],
+        hasher: &HVCHash,
+        progress: ProgressCallback,
+    ) -> Self {
+        let height = leaf_nodes.len().trailing_zeros() as usize + 1;
+        let mut nodes = vec![SmallPoly::default(); leaf_nodes.len() - 1];
+        nodes.extend_from_slice(leaf_nodes);
+        let counter = Counter::new(Stage::Nodes, leaf_nodes.len() - 1, progress);
 
-        // Compute the starting indices for each non-leaf level of the tree
-        let mut index = 0;
-        let mut level_indices = Vec::with_capacity(HEIGHT - 1);
-        for _ in 0..(HEIGHT - 1) {
-            level_indices.push(index);
-            index = left_child_index(index);
-        }
+        // compute the hash values for every non-leaf layer, from the bottom up;
+        // the layer beginning at `start_index` ends at `left_child_index(start_index)` (exclusive),
+        // where the layer of its children begins. Each layer is hashed in chunks
+        // of `CHUNK_SIZE` nodes, which run in parallel with the `parallel` feature.
+        for level in (0..height - 1).rev() {
+            let start_index = (1 << level) - 1;
+            let (parents, children) = nodes.split_at_mut(left_child_index(start_index));
+            let parents = &mut parents[start_index..];
+            let children = &*children;
+            let hash_chunk = |(chunk_index, chunk): (usize, &mut [SmallPoly])| {
+                let pairs = children[2 * chunk_index * CHUNK_SIZE..].chunks_exact(2);
+                for (node, pair) in chunk.iter_mut().zip(pairs) {
+                    *node = hasher.decom_then_hash(&pair[0], &pair[1]);
+                }
+                counter.add(chunk.len());
+            };
 
-        // compute the hash values for the non-leaf bottom layer
-        {
-            let start_index = level_indices.pop().unwrap();
-            let upper_bound = left_child_index(start_index);
-            for (current_index, e) in non_leaf_nodes
-                .iter_mut()
+            #[cfg(not(feature = "parallel"))]
+            parents
+                .chunks_mut(CHUNK_SIZE)
                 .enumerate()
-                .take(upper_bound)
-                .skip(start_index)
-            {
-                // `left_child_index(current_index)` and `right_child_index(current_index) returns the position of
-                // leaf in the whole tree (represented as a list in level order). We need to shift it
-                // by `-upper_bound` to get the index in `leaf_nodes` list.
-                let left_leaf_index = left_child_index(current_index) - upper_bound;
-                let right_leaf_index = right_child_index(current_index) - upper_bound;
-                // compute hash
-                *e = hasher
-                    .decom_then_hash(&leaf_nodes[left_leaf_index], &leaf_nodes[right_leaf_index]);
-            }
-        }
+                .for_each(hash_chunk);
 
-        // compute the hash values for nodes in every other layer in the tree
-        level_indices.reverse();
-        for &start_index in &level_indices {
-            // The layer beginning `start_index` ends at `upper_bound` (exclusive).
-            let upper_bound = left_child_index(start_index);
-            for current_index in start_index..upper_bound {
-                let left_index = left_child_index(current_index);
-                let right_index = right_child_index(current_index);
-                non_leaf_nodes[current_index] = hasher
-                    .decom_then_hash(&non_leaf_nodes[left_index], &non_leaf_nodes[right_index]);
-            }
+            #[cfg(feature = "parallel")]
+            parents
+                .par_chunks_mut(CHUNK_SIZE)
+                .enumerate()
+                .for_each(hash_chunk);
         }
 
         Self {
-            non_leaf_nodes,
-            leaf_nodes: leaf_nodes.to_vec(),
+            height,
+            nodes: Nodes::Memory(nodes),
+        }
+    }
+
+    /// read the node at the given position in level order
+    fn node(&self, index: usize) -> Result<SmallPoly> {
+        match &self.nodes {
+            Nodes::Memory(nodes) => Ok(nodes[index]),
+            Nodes::Mapped(map) => file::read_node(map, index),
         }
     }
 
     pub fn root(&self) -> SmallPoly {
-        self.non_leaf_nodes[0]
+        // the root of a mapped tree is checked when the file is opened
+        self.node(0).expect("the root is valid")
+    }
+
+    /// the leaf at the given index
+    pub(crate) fn leaf(&self, index: usize) -> Result<SmallPoly> {
+        if index >= self.num_leaves() {
+            return Err(Error::InvalidIndex(index));
+        }
+        self.node(convert_index_to_last_level(index, self.height))
+    }
+
+    /// the height of the tree, including the root and the leaves
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// the number of leaves, i.e., of one-time keys
+    pub fn num_leaves(&self) -> usize {
+        (1 << self.height) >> 1
+    }
+
+    /// whether the nodes are read from a memory-mapped file
+    pub fn is_mapped(&self) -> bool {
+        matches!(self.nodes, Nodes::Mapped(_))
     }
 
     // generate a membership proof for the given index
-    pub fn gen_proof(&self, index: usize) -> Path {
-        // Get Leaf hash, and leaf sibling hash,
-        let leaf_index_in_tree = convert_index_to_last_level(index, HEIGHT);
+    pub fn gen_proof(&self, index: usize) -> Result<Path> {
+        if index >= self.num_leaves() {
+            return Err(Error::InvalidIndex(index));
+        }
 
         // path.len() = `tree height - 1`, the missing elements being the root
-        let mut nodes = Vec::with_capacity(HEIGHT - 1);
-        if index % 2 == 0 {
-            nodes.push((self.leaf_nodes[index], self.leaf_nodes[index + 1]))
-        } else {
-            nodes.push((self.leaf_nodes[index - 1], self.leaf_nodes[index]))
-        }
+        let mut nodes = Vec::with_capacity(self.height - 1);
 
-        // Iterate from the bottom layer after the leaves, to the top, storing all nodes and their siblings.
-        let mut current_node = parent_index(leaf_index_in_tree).unwrap();
+        // Iterate from the leaf to the top, storing all nodes and their siblings.
+        let mut current_node = convert_index_to_last_level(index, self.height);
         while current_node != 0 {
             let sibling_node = sibling_index(current_node).unwrap();
             if is_left_child(current_node) {
-                nodes.push((
-                    self.non_leaf_nodes[current_node],
-                    self.non_leaf_nodes[sibling_node],
-                ));
+                nodes.push((self.node(current_node)?, self.node(sibling_node)?));
             } else {
-                nodes.push((
-                    self.non_leaf_nodes[sibling_node],
-                    self.non_leaf_nodes[current_node],
-                ));
+                nodes.push((self.node(sibling_node)?, self.node(current_node)?));
             }
             current_node = parent_index(current_node).unwrap();
         }
@@ -137,7 +207,35 @@ impl Tree {
         // we want to make path from root to bottom
         nodes.reverse();
 
-        Path { index, nodes }
+        Ok(Path { index, nodes })
+    }
+}
+
+impl Encode for Tree {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.push(self.height as u8);
+        match &self.nodes {
+            Nodes::Memory(nodes) => nodes.iter().for_each(|x| x.encode(out)),
+            Nodes::Mapped(map) => out.extend_from_slice(file::node_bytes(map)),
+        }
+    }
+}
+
+impl Decode for Tree {
+    fn decode_into(&mut self, reader: &mut Reader) -> Result<()> {
+        let height = reader.read_u8()? as usize;
+        check_height(height)?;
+        let num_nodes = (1 << height) - 1;
+        // reject truncated inputs before allocating the tree
+        if reader.remaining() < num_nodes * NODE_SIZE {
+            return Err(Error::InvalidTreeSize(reader.remaining() / NODE_SIZE));
+        }
+
+        let mut nodes = vec![SmallPoly::default(); num_nodes];
+        nodes.iter_mut().try_for_each(|x| x.decode_into(reader))?;
+        self.height = height;
+        self.nodes = Nodes::Memory(nodes);
+        Ok(())
     }
 }
 
@@ -200,12 +298,79 @@ mod test {
         let leafs: Vec<SmallPoly> = (0..(1 << (HEIGHT - 1)))
             .map(|_| SmallPoly::rand_poly(&mut rng))
             .collect();
-        let tree = Tree::new_with_leaf_nodes(&leafs, &hasher);
+        let tree = Tree::new_with_leaf_nodes(&leafs, &hasher).unwrap();
+        assert_eq!(
+            tree.gen_proof(1 << (HEIGHT - 1)).unwrap_err(),
+            Error::InvalidIndex(1 << (HEIGHT - 1))
+        );
 
         for _ in 0..100 {
             let index = rng.next_u32() % (1 << (HEIGHT - 1));
-            let proof = tree.gen_proof(index as usize);
-            assert!(proof.verify(&tree.non_leaf_nodes[0], &hasher));
+            let proof = tree.gen_proof(index as usize).unwrap();
+            assert!(proof.verify(&tree.root(), &hasher));
+        }
+    }
+
+    #[test]
+    fn test_small_tree() {
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+        let hasher = HVCHash::init(&mut rng);
+
+        for height in 2..6 {
+            let leafs: Vec<SmallPoly> = (0..(1 << (height - 1)))
+                .map(|_| SmallPoly::rand_poly(&mut rng))
+                .collect();
+            let tree = Tree::new_with_leaf_nodes(&leafs, &hasher).unwrap();
+            assert_eq!(tree.height(), height);
+            assert_eq!(tree.num_leaves(), leafs.len());
+
+            for index in 0..leafs.len() {
+                let proof = tree.gen_proof(index).unwrap();
+                assert_eq!(proof.nodes.len(), height - 1);
+                assert!(proof.verify(&tree.root(), &hasher));
+            }
+            assert_eq!(
+                tree.gen_proof(leafs.len()).unwrap_err(),
+                Error::InvalidIndex(leafs.len())
+            );
         }
+
+        let leafs = vec![SmallPoly::default(); 3];
+        assert_eq!(
+            Tree::new_with_leaf_nodes(&leafs, &hasher).unwrap_err(),
+            Error::InvalidTreeSize(3)
+        );
+    }
+
+    #[test]
+    fn test_progress() {
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+        let hasher = HVCHash::init(&mut rng);
+
+        // several chunks on the lower levels
+        let height = 9;
+        let leafs: Vec<SmallPoly> = (0..(1 << (height - 1)))
+            .map(|_| SmallPoly::rand_poly(&mut rng))
+            .collect();
+        let reports = std::sync::Mutex::new(Vec::new());
+        let tree =
+            Tree::new_with_progress(&leafs, &hasher, &|p| reports.lock().unwrap().push(p)).unwrap();
+
+        // the same nodes as hashing them one at a time
+        let mut level = leafs;
+        while level.len() > 1 {
+            level = level
+                .chunks_exact(2)
+                .map(|pair| hasher.decom_then_hash(&pair[0], &pair[1]))
+                .collect();
+        }
+        assert_eq!(tree.root(), level[0]);
+
+        let reports = reports.into_inner().unwrap();
+        let total = (1 << (height - 1)) - 1;
+        assert!(reports
+            .iter()
+            .all(|p| p.stage == Stage::Nodes && p.total == total && p.done <= total));
+        assert_eq!(reports.iter().map(|p| p.done).max(), Some(total));
     }
 }
@@ -1,4 +1,10 @@
-use crate::{hvc::hash::HVCHash, poly::SmallPoly, randomizer::Randomizers, RandomizedPath, HEIGHT};
+use crate::{
+    error::{Error, Result},
+    hvc::hash::HVCHash,
+    poly::SmallPoly,
+    randomizer::Randomizers,
+    RandomizedPath, HEIGHT,
+};
 use core::fmt;
 use std::fmt::Display;
 
@@ -71,13 +77,16 @@ impl Path {
     pub(crate) fn aggregate_with_randomizers(
         paths: &[Self],
         randomizers: &Randomizers,
-    ) -> RandomizedPath {
+    ) -> Result<RandomizedPath> {
         // check that length are correct
         let len = paths.len();
-        assert_eq!(len, randomizers.poly.len());
+        if len != randomizers.poly.len() {
+            return Err(Error::LengthMismatch(randomizers.poly.len(), len));
+        }
         // check that we aggregate for a same index
-        for e in paths.iter().skip(1) {
-            assert_eq!(e.index, paths[0].index)
+        let first = paths.first().ok_or(Error::EmptyInput)?;
+        if let Some(e) = paths.iter().find(|e| e.index != first.index) {
+            return Err(Error::MixedIndices(first.index, e.index));
         }
 
         let randomized_paths: Vec<RandomizedPath> = paths.iter().map(|x| x.into()).collect();
@@ -85,24 +94,30 @@ impl Path {
     }
 
     /// Aggregate a set of paths
-    pub fn aggregation(paths: &[Self], roots: &[SmallPoly]) -> RandomizedPath {
+    pub fn aggregation(paths: &[Self], roots: &[SmallPoly]) -> Result<RandomizedPath> {
         // get and apply the randomizers
         let randomizers = Randomizers::from_pks(roots);
         Self::aggregate_with_randomizers(paths, &randomizers)
     }
 
     pub fn random_for_testing<R: rand::Rng>(rng: &mut R, hasher: &HVCHash) -> (Self, SmallPoly) {
+        Self::random_for_testing_with_height(rng, hasher, HEIGHT)
+    }
+
+    pub fn random_for_testing_with_height<R: rand::Rng>(
+        rng: &mut R,
+        hasher: &HVCHash,
+        height: usize,
+    ) -> (Self, SmallPoly) {
         let mut nodes = vec![(SmallPoly::rand_poly(rng), SmallPoly::rand_poly(rng))];
 
-        for i in 1..HEIGHT - 1 {
+        for i in 1..height - 1 {
             let left = hasher.decom_then_hash(&nodes[i - 1].0, &nodes[i - 1].1);
             nodes.push((left, SmallPoly::rand_poly(rng)))
         }
-        let root = hasher.decom_then_hash(&nodes[HEIGHT - 2].0, &nodes[HEIGHT - 2].1);
+        let root = hasher.decom_then_hash(&nodes[height - 2].0, &nodes[height - 2].1);
         nodes.reverse();
-        let mut path = Self::default();
-        path.nodes.clone_from_slice(&nodes);
-        path.index = 0;
+        let path = Self { nodes, index: 0 };
 
         (path, root)
     }
@@ -150,8 +165,18 @@ mod test {
             roots.push(root);
         }
 
-        let path = Path::aggregation(&paths, &roots);
+        let path = Path::aggregation(&paths, &roots).unwrap();
+
+        assert!(path.verify(&roots, &hasher));
 
-        assert!(path.verify(&roots, &hasher))
+        assert_eq!(
+            Path::aggregation(&paths[1..], &roots).unwrap_err(),
+            Error::LengthMismatch(roots.len(), paths.len() - 1)
+        );
+        paths[1].index = 1;
+        assert_eq!(
+            Path::aggregation(&paths, &roots).unwrap_err(),
+            Error::MixedIndices(0, 1)
+        );
     }
 }
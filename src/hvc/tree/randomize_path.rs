@@ -2,7 +2,10 @@
 use rayon::iter::{IntoParallelRefMutIterator, ParallelIterator};
 
 use crate::{
+    codec::{check_decomposed, impl_serialization, Decode, Encode, Reader},
+    error::{Error, Result},
     hvc::hash::HVCHash,
+    param::check_height,
     poly::{SmallPoly, TerPolyCoeffEncoding},
     randomizer::Randomizers,
     Path, SignedPoly, HEIGHT, SMALL_MODULUS_BITS,
@@ -21,19 +24,22 @@ pub struct RandomizedPath {
 
 impl Default for RandomizedPath {
     fn default() -> Self {
+        // build the nodes on the heap directly; as an array
+        // they would take over a megabyte of stack
         Self {
-            nodes: [(
-                [SignedPoly::default(); SMALL_MODULUS_BITS],
-                [SignedPoly::default(); SMALL_MODULUS_BITS],
-            ); HEIGHT - 1]
-                .to_vec(),
+            nodes: vec![
+                (
+                    [SignedPoly::default(); SMALL_MODULUS_BITS],
+                    [SignedPoly::default(); SMALL_MODULUS_BITS],
+                );
+                HEIGHT - 1
+            ],
             index: 0,
             is_randomized: false,
         }
     }
 }
 
-// todo: improve the code to avoid `clone_from_slice`
 impl From<&Path> for RandomizedPath {
     fn from(p: &Path) -> Self {
         // seems that the overhead of parallelizing the conversion is enormous
@@ -53,12 +59,11 @@ impl From<&Path> for RandomizedPath {
             .iter()
             .map(|(left, right)| (left.decompose(), right.decompose()))
             .collect();
-        let mut res = Self::default();
-        res.nodes.clone_from_slice(&nodes);
-        res.index = p.index;
-        res.is_randomized = false;
-
-        res
+        Self {
+            nodes,
+            index: p.index,
+            is_randomized: false,
+        }
     }
 }
 
@@ -78,10 +83,10 @@ impl From<&RandomizedPath> for Path {
             .iter()
             .map(|(left, right)| (SmallPoly::projection(left), SmallPoly::projection(right)))
             .collect();
-        let mut res = Self::default();
-        res.nodes.clone_from_slice(&nodes);
-        res.index = r.index;
-        res
+        Self {
+            nodes,
+            index: r.index,
+        }
     }
 }
 
@@ -96,9 +101,19 @@ impl RandomizedPath {
             .rev()
     }
 
-    pub fn randomize_with(&mut self, ternary: &SignedPoly) {
+    /// the largest absolute value of the coefficients
+    pub fn infinity_norm(&self) -> u32 {
+        self.nodes
+            .iter()
+            .flat_map(|(left, right)| left.iter().chain(right.iter()))
+            .map(|x| x.infinity_norm())
+            .max()
+            .unwrap_or_default()
+    }
+
+    pub fn randomize_with(&mut self, ternary: &SignedPoly) -> Result<()> {
         if self.is_randomized {
-            panic!("already randomized")
+            return Err(Error::AlreadyRandomized);
         }
         let ternary_coeffs: TerPolyCoeffEncoding = ternary.into();
 
@@ -121,14 +136,26 @@ impl RandomizedPath {
         });
 
         self.is_randomized = true;
+        Ok(())
     }
 
-    pub(crate) fn aggregate_with_randomizers(paths: &[Self], randomizers: &Randomizers) -> Self {
+    pub(crate) fn aggregate_with_randomizers(
+        paths: &[Self],
+        randomizers: &Randomizers,
+    ) -> Result<Self> {
+        if paths.len() != randomizers.poly.len() {
+            return Err(Error::LengthMismatch(randomizers.poly.len(), paths.len()));
+        }
+        let first = paths.first().ok_or(Error::EmptyInput)?;
+        if let Some(e) = paths.iter().find(|e| e.index != first.index) {
+            return Err(Error::MixedIndices(first.index, e.index));
+        }
+
         let mut randomized_paths: Vec<RandomizedPath> = paths.to_vec();
         randomized_paths
             .iter_mut()
             .zip(randomizers.poly.iter())
-            .for_each(|(path, randomizer)| path.randomize_with(randomizer));
+            .try_for_each(|(path, randomizer)| path.randomize_with(randomizer))?;
 
         // aggregate the result
         let mut res = randomized_paths[0].clone();
@@ -137,13 +164,25 @@ impl RandomizedPath {
             .iter()
             .skip(1)
             .for_each(|target| res = &res + target);
-        res
+        Ok(res)
     }
 
     /// verifies the path against a list of root
     pub fn verify(&self, roots: &[SmallPoly], hasher: &HVCHash) -> bool {
+        self.verify_with_randomizers(roots, &Randomizers::from_pks(roots), hasher)
+    }
+
+    /// verifies the path against a list of root, aggregated with the given randomizers
+    pub fn verify_with_randomizers(
+        &self,
+        roots: &[SmallPoly],
+        randomziers: &Randomizers,
+        hasher: &HVCHash,
+    ) -> bool {
+        if roots.len() != randomziers.poly.len() {
+            return false;
+        }
         // recompute the root
-        let randomziers = Randomizers::from_pks(roots);
         let mut root = SmallPoly::default();
         roots
             .iter()
@@ -173,6 +212,43 @@ impl RandomizedPath {
     }
 }
 
+impl Encode for RandomizedPath {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&(self.index as u64).to_le_bytes());
+        self.is_randomized.encode(out);
+        out.extend_from_slice(&(self.nodes.len() as u32).to_le_bytes());
+        for (left, right) in self.nodes.iter() {
+            left.encode(out);
+            right.encode(out);
+        }
+    }
+}
+
+impl Decode for RandomizedPath {
+    fn decode_into(&mut self, reader: &mut Reader) -> Result<()> {
+        self.index = reader.read_u64()? as usize;
+        self.is_randomized = reader.read_bool()?;
+        let len = reader.read_u32()? as usize;
+        if check_height(len + 1).is_err() {
+            return Err(Error::InvalidPathLength(len));
+        }
+        if self.index >> len != 0 {
+            return Err(Error::InvalidIndex(self.index));
+        }
+
+        self.nodes.resize(len, Default::default());
+        for (left, right) in self.nodes.iter_mut() {
+            left.decode_into(reader)?;
+            right.decode_into(reader)?;
+            check_decomposed(left, self.is_randomized)?;
+            check_decomposed(right, self.is_randomized)?;
+        }
+        Ok(())
+    }
+}
+
+impl_serialization!(RandomizedPath);
+
 impl Add for &RandomizedPath {
     type Output = RandomizedPath;
 
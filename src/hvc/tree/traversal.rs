@@ -0,0 +1,429 @@
+//! Merkle tree traversal: produces the paths of consecutive leaves while keeping
+//! O(HEIGHT^2) nodes instead of the whole tree.
+//!
+//! For each level we keep the current authentication node, i.e., the sibling of
+//! the on-path node. When it changes to a left node, that node is the on-path
+//! node of the previous leaf. When it changes to a right node, it has been
+//! computed in advance by a tree hash instance over the leaves below it, which
+//! is given one leaf per signature. Each signature thus costs at most
+//! `HEIGHT - 1` leaf computations and `O(HEIGHT)` hashes.
+
+use super::path::Path;
+use crate::{
+    codec::{Decode, Encode, Reader},
+    error::{Error, Result},
+    hvc::hash::HVCHash,
+    param::check_height,
+    poly::SmallPoly,
+};
+#[cfg(feature = "parallel")]
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+// number of leaves computed at once when building the root
+const BATCH_SIZE: usize = 1 << 10;
+
+/// Computes a node from the leaves below it, one leaf at a time, keeping at most
+/// one node per level.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct TreeHash {
+    next_leaf: usize,
+    /// pending nodes with their levels, increasing from the bottom of the stack
+    stack: Vec<(usize, SmallPoly)>,
+}
+
+impl TreeHash {
+    fn new(first_leaf: usize) -> Self {
+        Self {
+            next_leaf: first_leaf,
+            stack: Vec::new(),
+        }
+    }
+
+    /// whether the node at `level` has been computed
+    fn is_complete(&self, level: usize) -> bool {
+        self.stack.len() == 1 && self.stack[0].0 == level
+    }
+
+    /// add the next leaf, and hash all completed pairs of nodes;
+    /// `visit` is called with the level, the index within the level, and the
+    /// value of each new node
+    fn push(
+        &mut self,
+        leaf: SmallPoly,
+        hasher: &HVCHash,
+        mut visit: impl FnMut(usize, usize, &SmallPoly),
+    ) {
+        let index = self.next_leaf;
+        self.next_leaf += 1;
+        visit(0, index, &leaf);
+
+        let mut node = (0, leaf);
+        while let Some(&(level, left)) = self.stack.last() {
+            if level != node.0 {
+                break;
+            }
+            self.stack.pop();
+            node = (level + 1, hasher.decom_then_hash(&left, &node.1));
+            visit(level + 1, index >> (level + 1), &node.1);
+        }
+        self.stack.push(node);
+    }
+}
+
+/// The state of a tree traversal, ready to produce the path of the next leaf.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TreeTraversal {
+    height: usize,
+    /// the next leaf whose path will be produced
+    index: usize,
+    /// the authentication nodes of `index`, from the leaf level upwards
+    auth: Vec<SmallPoly>,
+    /// the next right authentication node of each level, if one is being computed
+    treehash: Vec<Option<TreeHash>>,
+}
+
+impl TreeTraversal {
+    /// Compute the root of a tree of the given height, where `leaf(i)` is the
+    /// i-th leaf, and the traversal state for its first leaf.
+    #[cfg(not(feature = "parallel"))]
+    pub fn new(
+        height: usize,
+        leaf: impl Fn(usize) -> SmallPoly,
+        hasher: &HVCHash,
+    ) -> Result<(SmallPoly, Self)> {
+        Self::new_with_batches(height, hasher, |range| range.map(&leaf).collect())
+    }
+
+    /// Compute the root of a tree of the given height, where `leaf(i)` is the
+    /// i-th leaf, and the traversal state for its first leaf.
+    #[cfg(feature = "parallel")]
+    pub fn new(
+        height: usize,
+        leaf: impl Fn(usize) -> SmallPoly + Sync,
+        hasher: &HVCHash,
+    ) -> Result<(SmallPoly, Self)> {
+        Self::new_with_batches(height, hasher, |range| {
+            range.into_par_iter().map(&leaf).collect()
+        })
+    }
+
+    fn new_with_batches(
+        height: usize,
+        hasher: &HVCHash,
+        batch: impl Fn(std::ops::Range<usize>) -> Vec<SmallPoly>,
+    ) -> Result<(SmallPoly, Self)> {
+        check_height(height)?;
+        let num_leaves = 1 << (height - 1);
+
+        // the initial authentication path consists of the second node of each level
+        let mut auth = vec![SmallPoly::default(); height - 1];
+        let mut treehash = TreeHash::new(0);
+        for start in (0..num_leaves).step_by(BATCH_SIZE) {
+            let end = num_leaves.min(start + BATCH_SIZE);
+            for leaf in batch(start..end) {
+                treehash.push(leaf, hasher, |level, index, node| {
+                    if index == 1 {
+                        auth[level] = *node;
+                    }
+                });
+            }
+        }
+
+        Ok((
+            treehash.stack[0].1,
+            Self {
+                height,
+                index: 0,
+                auth,
+                treehash: vec![None; height - 1],
+            },
+        ))
+    }
+
+    /// the height of the tree, including the root and the leaves
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// the number of leaves
+    pub fn num_leaves(&self) -> usize {
+        1 << (self.height - 1)
+    }
+
+    /// the leaf whose path is produced next
+    pub fn next_index(&self) -> usize {
+        self.index
+    }
+
+    /// Produce the path of leaf `next_index()`, whose value is `leaf_node`, and
+    /// move on to the following leaf. `leaf(i)` must compute the i-th leaf.
+    #[cfg(not(feature = "parallel"))]
+    pub fn next_path(
+        &mut self,
+        leaf_node: &SmallPoly,
+        leaf: impl Fn(usize) -> SmallPoly,
+        hasher: &HVCHash,
+    ) -> Result<Path> {
+        self.next_path_with_leaves(leaf_node, hasher, |indices| {
+            indices.into_iter().map(&leaf).collect()
+        })
+    }
+
+    /// Produce the path of leaf `next_index()`, whose value is `leaf_node`, and
+    /// move on to the following leaf. `leaf(i)` must compute the i-th leaf.
+    #[cfg(feature = "parallel")]
+    pub fn next_path(
+        &mut self,
+        leaf_node: &SmallPoly,
+        leaf: impl Fn(usize) -> SmallPoly + Sync,
+        hasher: &HVCHash,
+    ) -> Result<Path> {
+        self.next_path_with_leaves(leaf_node, hasher, |indices| {
+            indices.into_par_iter().map(&leaf).collect()
+        })
+    }
+
+    fn next_path_with_leaves(
+        &mut self,
+        leaf_node: &SmallPoly,
+        hasher: &HVCHash,
+        leaves: impl Fn(Vec<usize>) -> Vec<SmallPoly>,
+    ) -> Result<Path> {
+        let index = self.index;
+        if index >= self.num_leaves() {
+            return Err(Error::InvalidIndex(index));
+        }
+        let depth = self.height - 1;
+
+        // hash up from the leaf, pairing each on-path node with its sibling
+        let mut on_path = Vec::with_capacity(depth);
+        let mut nodes = Vec::with_capacity(depth);
+        let mut node = *leaf_node;
+        for (level, auth) in self.auth.iter().enumerate() {
+            on_path.push(node);
+            let pair = if (index >> level) & 1 == 0 {
+                (node, *auth)
+            } else {
+                (*auth, node)
+            };
+            nodes.push(pair);
+            if level + 1 < depth {
+                node = hasher.decom_then_hash(&pair.0, &pair.1);
+            }
+        }
+        nodes.reverse();
+
+        // give each pending tree hash one more leaf
+        let pending: Vec<usize> = self
+            .treehash
+            .iter()
+            .enumerate()
+            .filter_map(|(level, t)| t.as_ref().filter(|t| !t.is_complete(level)))
+            .map(|t| t.next_leaf)
+            .collect();
+        let mut new_leaves = leaves(pending).into_iter();
+        for (level, t) in self.treehash.iter_mut().enumerate() {
+            if let Some(t) = t.as_mut().filter(|t| !t.is_complete(level)) {
+                let leaf = new_leaves.next().ok_or(Error::InconsistentState)?;
+                t.push(leaf, hasher, |_, _, _| ());
+            }
+        }
+
+        // update the authentication nodes that change for the next leaf
+        let next = index + 1;
+        if next < self.num_leaves() {
+            // these are the levels where the on-path node changes
+            let num_levels = next.trailing_zeros() as usize + 1;
+            for (level, node) in on_path.into_iter().enumerate().take(num_levels) {
+                let position = next >> level;
+                if position & 1 == 1 {
+                    // the left sibling is the on-path node of the current leaf;
+                    // start computing the right sibling after it
+                    self.auth[level] = node;
+                    let target = position + 2;
+                    if target < 1 << (depth - level) {
+                        self.treehash[level] = Some(TreeHash::new(target << level));
+                    }
+                } else {
+                    let t = self.treehash[level]
+                        .take()
+                        .filter(|t| t.is_complete(level))
+                        .ok_or(Error::InconsistentState)?;
+                    self.auth[level] = t.stack[0].1;
+                }
+            }
+        }
+        self.index = next;
+
+        Ok(Path { nodes, index })
+    }
+
+    /// The position of the next leaf and the levels of the stack of the tree
+    /// hash at `level` when the path of `self.index` is produced next, if there
+    /// is one. It computes the right sibling at `level` of the block of
+    /// `self.index`, and has been given one leaf per path since the start of
+    /// the block.
+    fn expected_treehash(&self, level: usize) -> Option<(usize, Vec<usize>)> {
+        let position = self.index >> level;
+        let target = position + 2;
+        if position & 1 == 0 || target >= 1 << (self.height - 1 - level) {
+            return None;
+        }
+        let count = self.index & ((1 << level) - 1);
+        let levels = (0..level).rev().filter(|l| (count >> l) & 1 == 1).collect();
+        Some(((target << level) + count, levels))
+    }
+}
+
+impl Encode for TreeTraversal {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.push(self.height as u8);
+        out.extend_from_slice(&(self.index as u64).to_le_bytes());
+        self.auth.iter().for_each(|x| x.encode(out));
+        for t in self.treehash.iter() {
+            match t {
+                None => out.push(0),
+                Some(t) => {
+                    out.push(1);
+                    out.extend_from_slice(&(t.next_leaf as u64).to_le_bytes());
+                    out.push(t.stack.len() as u8);
+                    for (level, node) in t.stack.iter() {
+                        out.push(*level as u8);
+                        node.encode(out);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Decode for TreeTraversal {
+    fn decode_into(&mut self, reader: &mut Reader) -> Result<()> {
+        self.height = reader.read_u8()? as usize;
+        check_height(self.height)?;
+        self.index = reader.read_u64()? as usize;
+        if self.index > self.num_leaves() {
+            return Err(Error::InvalidIndex(self.index));
+        }
+        let depth = self.height - 1;
+        self.auth = vec![SmallPoly::default(); depth];
+        self.auth
+            .iter_mut()
+            .try_for_each(|x| x.decode_into(reader))?;
+
+        self.treehash = vec![None; depth];
+        for level in 0..depth {
+            match reader.read_u8()? {
+                0 => (),
+                1 => {
+                    let next_leaf = reader.read_u64()? as usize;
+                    if next_leaf >= self.num_leaves() {
+                        return Err(Error::InvalidIndex(next_leaf));
+                    }
+                    // a tree hash for `level` never holds more than one node per level
+                    let len = reader.read_u8()? as usize;
+                    if len > level + 1 {
+                        return Err(Error::InvalidPathLength(len));
+                    }
+                    let mut stack = vec![(0, SmallPoly::default()); len];
+                    for (node_level, node) in stack.iter_mut() {
+                        *node_level = reader.read_u8()? as usize;
+                        if *node_level > level {
+                            return Err(Error::InvalidHeight(*node_level));
+                        }
+                        node.decode_into(reader)?;
+                    }
+                    self.treehash[level] = Some(TreeHash { next_leaf, stack });
+                }
+                flag => return Err(Error::InvalidFlag(flag)),
+            }
+        }
+
+        // the tree hashes are determined by the index, except for their nodes
+        for (level, t) in self.treehash.iter().enumerate() {
+            let found = t.as_ref().map(|t| {
+                let levels: Vec<usize> = t.stack.iter().map(|(l, _)| *l).collect();
+                (t.next_leaf, levels)
+            });
+            if found != self.expected_treehash(level) {
+                return Err(Error::InconsistentState);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{codec, hvc::tree::Tree};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    #[test]
+    fn test_traversal() {
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+        let hasher = HVCHash::init(&mut rng);
+
+        for height in 2..7 {
+            let leafs: Vec<SmallPoly> = (0..(1 << (height - 1)))
+                .map(|_| SmallPoly::rand_poly(&mut rng))
+                .collect();
+            let tree = Tree::new_with_leaf_nodes(&leafs, &hasher).unwrap();
+            let leaf = |i: usize| leafs[i];
+
+            let (root, mut traversal) = TreeTraversal::new(height, leaf, &hasher).unwrap();
+            assert_eq!(root, tree.root());
+            for (index, node) in leafs.iter().enumerate() {
+                assert_eq!(traversal.next_index(), index);
+                // the state survives serialization at any point
+                traversal = codec::from_bytes(&codec::to_bytes(&traversal)).unwrap();
+                let path = traversal.next_path(node, leaf, &hasher).unwrap();
+                assert_eq!(path, tree.gen_proof(index).unwrap());
+            }
+            assert_eq!(
+                traversal.next_path(&leafs[0], leaf, &hasher).unwrap_err(),
+                Error::InvalidIndex(leafs.len())
+            );
+        }
+
+        assert_eq!(
+            TreeTraversal::new(1, |_| SmallPoly::default(), &hasher).unwrap_err(),
+            Error::InvalidHeight(1)
+        );
+    }
+
+    #[test]
+    fn test_inconsistent_state() {
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+        let hasher = HVCHash::init(&mut rng);
+        let leafs: Vec<SmallPoly> = (0..16).map(|_| SmallPoly::rand_poly(&mut rng)).collect();
+        let leaf = |i: usize| leafs[i];
+        let (_, mut traversal) = TreeTraversal::new(5, leaf, &hasher).unwrap();
+        for node in leafs[..5].iter() {
+            traversal.next_path(node, leaf, &hasher).unwrap();
+        }
+        let decode = |t: &TreeTraversal| codec::from_bytes::<TreeTraversal>(&codec::to_bytes(t));
+        assert_eq!(decode(&traversal).unwrap(), traversal);
+
+        // a tree hash that is missing, or not where the index expects it
+        let mut missing = traversal.clone();
+        missing.treehash[0] = None;
+        assert_eq!(decode(&missing).unwrap_err(), Error::InconsistentState);
+        let mut moved = traversal.clone();
+        moved.treehash[2].as_mut().unwrap().next_leaf += 1;
+        assert_eq!(decode(&moved).unwrap_err(), Error::InconsistentState);
+        let mut extra = traversal.clone();
+        extra.treehash[1] = Some(TreeHash::new(8));
+        assert_eq!(decode(&extra).unwrap_err(), Error::InconsistentState);
+        let mut skipped = traversal.clone();
+        skipped.index += 2;
+        assert_eq!(decode(&skipped).unwrap_err(), Error::InconsistentState);
+
+        // such a state fails to produce paths instead of panicking
+        assert_eq!(
+            missing.next_path(&leafs[5], leaf, &hasher).unwrap_err(),
+            Error::InconsistentState
+        );
+    }
+}
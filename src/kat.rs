@@ -0,0 +1,152 @@
+//! Known-answer tests.
+//!
+//! The values below are derived from fixed seeds and compared against the
+//! committed file `kat/squirrel.kat`, so that any change to the samplers, the
+//! hashes, the NTT or the encodings is noticed. Each line of the file is
+//! `name = hex`; small values are given in full and large ones as the SHA-256
+//! digest of their canonical encoding. Run the test with `SQUIRREL_UPDATE_KAT=1`
+//! to regenerate the file after an intended change.
+
+use crate::{
+    codec::{to_bytes, Encode},
+    hots::{HomomorphicOneTimeSignature, HotsParam},
+    randomizer::Randomizers,
+    smsig::test::with_large_stack,
+    HOTSHash, HVCHash, MultiSig, RandomizedHOTSPK, RandomizedPath, SMSigParam, SMSigScheme,
+    SmallPoly, Tree, DEFAULT_SETUP_SEED, HOTS, SCHEME_ID,
+};
+use sha2::{Digest, Sha256};
+use std::{fmt::Write, fs, path::PathBuf};
+
+const HEIGHT: usize = 4;
+const MESSAGE: &[u8] = b"Squirrel known-answer test";
+const NUM_SIGNERS: u8 = 3;
+const HOTS_COUNTERS: [usize; 4] = [0, 1, 2, 7];
+const INDICES: [usize; 2] = [0, 5];
+
+fn kat_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("kat/squirrel.kat")
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn digest<T: Encode>(value: &T) -> String {
+    hex(&Sha256::digest(to_bytes(value)))
+}
+
+fn randomizers_digest(randomizers: &Randomizers) -> String {
+    let bytes: Vec<u8> = randomizers.poly.iter().flat_map(to_bytes).collect();
+    hex(&Sha256::digest(bytes))
+}
+
+fn signer_seed(signer: u8) -> [u8; 32] {
+    [signer; 32]
+}
+
+#[derive(Default)]
+struct Kat(String);
+
+impl Kat {
+    fn line(&mut self, name: &str, value: impl AsRef<str>) {
+        writeln!(self.0, "{} = {}", name, value.as_ref()).unwrap();
+    }
+}
+
+fn generate() -> String {
+    let mut kat = Kat::default();
+    kat.0
+        .push_str("# generated by `SQUIRREL_UPDATE_KAT=1 cargo test kat`\n");
+
+    // parameters
+    let pp = SMSigParam::setup_from_seed(DEFAULT_SETUP_SEED, HEIGHT).unwrap();
+    let hvc_hasher = HVCHash::from_seed(DEFAULT_SETUP_SEED);
+    let hots_hasher = HOTSHash::from_seed(DEFAULT_SETUP_SEED);
+    let hots_param = HotsParam::from_seed(DEFAULT_SETUP_SEED);
+    kat.line("param.seed", hex(DEFAULT_SETUP_SEED));
+    kat.line("param.height", HEIGHT.to_string());
+    kat.line("param.hvc_hash", digest(&hvc_hasher));
+    kat.line("param.hots_hash", digest(&hots_hasher));
+    kat.line("param.hots_param", digest(&hots_param));
+    kat.line("param.fingerprint", hex(&pp.fingerprint()));
+    kat.line("message", hex(MESSAGE));
+
+    // one-time signatures
+    let seed = signer_seed(1);
+    kat.line("hots.seed", hex(&seed));
+    for counter in HOTS_COUNTERS {
+        let (pk, sk) = HOTS::key_gen(&seed, counter, &hots_param);
+        let sig = HOTS::sign(&sk, MESSAGE);
+        assert!(HOTS::verify(&pk, MESSAGE, &sig, &hots_param));
+        let name = format!("hots.{}", counter);
+        kat.line(
+            &format!("{}.pk", name),
+            digest(&RandomizedHOTSPK::from(&pk)),
+        );
+        kat.line(
+            &format!("{}.pk_digest", name),
+            hex(&to_bytes(&pk.digest(&hots_hasher))),
+        );
+        kat.line(&format!("{}.sig", name), digest(&sig));
+    }
+
+    // keys, paths and signatures of each signer
+    let mut roots = Vec::new();
+    let mut sigs = vec![Vec::new(); INDICES.len()];
+    for signer in 1..=NUM_SIGNERS {
+        let seed = signer_seed(signer);
+        let leaves: Vec<SmallPoly> = (0..1 << (HEIGHT - 1))
+            .map(|i| HOTS::key_gen(&seed, i, &hots_param).0.digest(&hots_hasher))
+            .collect();
+        let tree = Tree::new_with_leaf_nodes(&leaves, &hvc_hasher).unwrap();
+        let (pk, sk) = SMSigScheme::key_gen(&seed, &pp);
+        assert_eq!(pk, tree.root());
+
+        let name = format!("signer.{}", signer);
+        kat.line(&format!("{}.seed", name), hex(&seed));
+        kat.line(&format!("{}.root", name), hex(&to_bytes(&pk)));
+        for (sigs, index) in sigs.iter_mut().zip(INDICES) {
+            let path = RandomizedPath::from(&tree.gen_proof(index).unwrap());
+            let sig = SMSigScheme::sign(&sk, index, MESSAGE, &pp).unwrap();
+            assert!(SMSigScheme::verify(&pk, MESSAGE, &sig, &pp));
+            kat.line(&format!("{}.{}.path", name, index), digest(&path));
+            kat.line(&format!("{}.{}.sig", name, index), digest(&sig));
+            sigs.push(sig);
+        }
+        roots.push(pk);
+    }
+
+    // aggregation, with the current and the original randomizers
+    let randomizers = |index| Randomizers::from_pks_and_message(SCHEME_ID, &roots, MESSAGE, index);
+    let legacy = Randomizers::from_pks(&roots);
+    kat.line("aggregate.legacy.randomizers", randomizers_digest(&legacy));
+    for (sigs, index) in sigs.iter().zip(INDICES) {
+        let agg_sig = SMSigScheme::aggregate(sigs, &roots, MESSAGE).unwrap();
+        assert!(SMSigScheme::batch_verify(&roots, MESSAGE, &agg_sig, &pp));
+        let legacy_agg_sig = SMSigScheme::aggregate_with_randomizers(sigs, &legacy).unwrap();
+        let name = format!("aggregate.{}", index);
+        kat.line(
+            &format!("{}.randomizers", name),
+            randomizers_digest(&randomizers(index)),
+        );
+        kat.line(&format!("{}.sig", name), digest(&agg_sig));
+        kat.line(&format!("{}.legacy_sig", name), digest(&legacy_agg_sig));
+    }
+    kat.0
+}
+
+#[test]
+fn test_kat() {
+    with_large_stack(|| {
+        let generated = generate();
+        if std::env::var_os("SQUIRREL_UPDATE_KAT").is_some() {
+            fs::write(kat_path(), &generated).unwrap();
+        }
+        let expected = fs::read_to_string(kat_path()).unwrap();
+        for (expected, generated) in expected.lines().zip(generated.lines()) {
+            assert_eq!(generated, expected);
+        }
+        assert_eq!(generated.lines().count(), expected.lines().count());
+    })
+}
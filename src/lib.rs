@@ -1,22 +1,38 @@
 #![allow(dead_code)]
 
+mod codec;
+mod ct;
+mod durable;
+mod error;
 mod hots;
 mod hvc;
+#[cfg(test)]
+mod kat;
 mod param;
 mod poly;
+mod progress;
 mod randomizer;
 mod smsig;
+mod xof;
 
+pub use codec::ENCODING_VERSION;
+pub use error::{Error, Result};
 pub use hots::HOTSHash;
 pub use hots::HOTS;
+pub use hots::{HotsSig, RandomizedHOTSPK};
 pub use hvc::HVCHash;
 pub use hvc::RandomizedPath;
-pub use hvc::{Path, Tree};
+pub use hvc::{Path, Tree, TreeTraversal};
 pub use param::*;
 pub use poly::SignedPoly;
 pub use poly::*;
+pub use progress::{Progress, ProgressCallback, Stage};
 pub use randomizer::Randomizers;
-pub use smsig::SMSigScheme;
+pub use smsig::{
+    AggregateItem, Aggregator, Clock, ProofOfPossession, SMSigLazySK, SMSigPK, SMSigParam, SMSigSK,
+    SMSigScheme, SMSignature, SignerBitmap, SlotSchedule, StatefulSigner, SubsetSignature,
+    SystemClock, SCHEME_ID,
+};
 
 use rand::Rng;
 
@@ -30,11 +46,20 @@ pub trait MultiSig {
 
     fn key_gen(seed: &[u8; 32], pp: &Self::Param) -> (Self::PK, Self::SK);
 
-    fn sign(sk: &Self::SK, index: usize, message: &[u8], pp: &Self::Param) -> Self::Signature;
+    fn sign(
+        sk: &Self::SK,
+        index: usize,
+        message: &[u8],
+        pp: &Self::Param,
+    ) -> Result<Self::Signature>;
 
     fn verify(pk: &Self::PK, message: &[u8], sig: &Self::Signature, pp: &Self::Param) -> bool;
 
-    fn aggregate(sigs: &[Self::Signature], roots: &[SmallPoly]) -> Self::Signature;
+    fn aggregate(
+        sigs: &[Self::Signature],
+        roots: &[SmallPoly],
+        message: &[u8],
+    ) -> Result<Self::Signature>;
 
     fn batch_verify(
         pks: &[Self::PK],
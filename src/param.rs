@@ -1,4 +1,5 @@
-// degree of polynomial
+// degree of polynomial;
+// the ring parameters are fixed, since the NTT in `cpoly` is compiled for them
 pub const N: usize = 512;
 
 // non-zero entries in a randomizer polynomial
@@ -13,9 +14,19 @@ pub const SMALL_MODULUS_BITS: usize = 14;
 // the largest multiple of q that is smaller than 2^32
 pub const SMALL_SAMPLE_THRESHOLD: u32 = 4294956344;
 
-// height of the tree for the HVC scheme
+// the public string from which the standard parameters are derived,
+// see `SMSigParam::setup_from_seed`
+pub const DEFAULT_SETUP_SEED: &[u8] =
+    b"Squirrel: Efficient Synchronized Multi-Signatures from Lattices";
+
+// default height of the tree for the HVC scheme
 pub const HEIGHT: usize = 21;
 
+// range of tree heights that can be selected at runtime;
+// leaf indices are encoded with at most 32 bits
+pub const MIN_HEIGHT: usize = 2;
+pub const MAX_HEIGHT: usize = 33;
+
 // q for the larger ring, HOTS modulus
 pub const LARGE_MODULUS: u32 = 0x662801;
 
@@ -36,3 +47,49 @@ pub(crate) const TWO_BETA_S_PLUS_ONE: u32 = 89;
 
 // the largest multiple of (2*beta_s-1) that is smaller than 2^32
 pub(crate) const BETA_S_SAMPLE_THRESHOLD: u32 = 4294967251;
+
+// norm bound for a fresh HOTS signature sigma = s0 * hm + s1:
+// s0 has coefficients in [-beta_s, beta_s], hm has beta_s non-zero ternary entries,
+// and s1 is ternary
+pub(crate) const BETA_SIGMA: u32 = (BETA_S * BETA_S + 1) as u32;
+
+// security parameter used for the tail bound of aggregated coefficients
+pub(crate) const SECURITY_BITS: f64 = 128.0;
+
+/// Norm bound for an aggregated HOTS signature over `num_signers` signers.
+///
+/// This is `\beta_\sigma = 2 \rho \alpha \beta_s` from `scripts/param*.sage`,
+/// floored by the worst case of a single randomized fresh signature so that
+/// small committees are not rejected.
+pub fn beta_sigma_agg(num_signers: usize) -> u32 {
+    let paper_bound = 2 * num_signers * ALPHA * BETA_S;
+    let single_signer_bound = ALPHA * BETA_SIGMA as usize;
+    paper_bound.max(single_signer_bound).min(u32::MAX as usize) as u32
+}
+
+/// Norm bound for the coefficients of aggregated `RandomizedPath`s and
+/// `RandomizedHOTSPK`s over `num_signers` signers, for trees of `height`.
+///
+/// Each aggregated coefficient is a sum of `ALPHA * num_signers` terms in {-1, 0, 1}.
+/// Following `scripts/beta_agg_bound.sage`, we use the Hoeffding tail bound so that the
+/// probability of any honest coefficient exceeding the bound is below 2^-128, and
+/// cap it with the worst case `ALPHA * num_signers`.
+pub fn beta_agg(num_signers: usize, height: usize) -> u32 {
+    let worst_case = ALPHA * num_signers;
+
+    // number of coefficients that are checked against the bound
+    let num_coeffs = (N * LARGE_MODULUS_BITS * (2 * height + 1)) as f64;
+    let log_failure = (SECURITY_BITS + 1.0) * core::f64::consts::LN_2 + num_coeffs.ln();
+    let tail_bound = (2.0 * worst_case as f64 * log_failure).sqrt().ceil() as usize;
+
+    worst_case.min(tail_bound).min(u32::MAX as usize) as u32
+}
+
+/// Check that a tree height is in `MIN_HEIGHT..=MAX_HEIGHT`.
+pub(crate) fn check_height(height: usize) -> crate::error::Result<()> {
+    if (MIN_HEIGHT..=MAX_HEIGHT).contains(&height) {
+        Ok(())
+    } else {
+        Err(crate::error::Error::InvalidHeight(height))
+    }
+}
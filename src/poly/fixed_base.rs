@@ -0,0 +1,180 @@
+//! Fixed-base evaluation of the hashes.
+//!
+//! Both hashes compute `sum_i h_i * x_i` for fixed bases `h_i`, kept in NTT
+//! form, where the inputs `x_i` are usually binary: the bit decompositions of
+//! tree nodes or of HOTS public keys. The first `LAYERS` layers of the forward
+//! NTT map the coefficients `x[t * STRIDE + r]`, for `t < BLOCKS`, to the ones
+//! at `b * STRIDE + r`, for `b < BLOCKS`, with the same linear map for every
+//! `r`. On binary inputs this map is a subset sum of its columns, so the sums
+//! are tabulated per byte of input bits at compile time: each coefficient then
+//! takes two lookups instead of `LAYERS` butterflies.
+
+use super::SmallNTTPoly;
+use crate::{N, SMALL_MODULUS};
+use cpoly::HVC_NTT_TABLE;
+
+/// How a hasher evaluates the products of its inputs with its fixed bases.
+/// Both modes give the same digests.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HashMode {
+    /// A forward NTT of every input.
+    #[default]
+    Ntt,
+    /// Precomputed subset sums for the first NTT layers of binary inputs, and
+    /// a single reduction of the sum of the products. Inputs that are not
+    /// binary, e.g. in aggregated paths, fall back to the NTT.
+    FixedBase,
+}
+
+const Q: u32 = SMALL_MODULUS as u32;
+const LAYERS: usize = 4;
+const BLOCKS: usize = 1 << LAYERS;
+const STRIDE: usize = N >> LAYERS;
+const BYTES: usize = BLOCKS / 8;
+
+/// `TABLE[k][bits][b]` is the sum of the columns `8k + t` of the first
+/// layers, for the bits `t` set in `bits`, at block `b`.
+static TABLE: [[[u16; BLOCKS]; 256]; BYTES] = subset_sums();
+
+const fn subset_sums() -> [[[u16; BLOCKS]; 256]; BYTES] {
+    // the first layers of `hvc_ntt` on each unit vector, with `STRIDE` = 1
+    let mut columns = [[0u32; BLOCKS]; BLOCKS];
+    let mut t = 0;
+    while t < BLOCKS {
+        columns[t][t] = 1;
+        let mut l = 0;
+        while l < LAYERS {
+            let half = BLOCKS >> (l + 1);
+            let mut i = 0;
+            while i < 1 << l {
+                let s = HVC_NTT_TABLE[(1 << l) + i] as u32;
+                let mut j = 2 * i * half;
+                while j < (2 * i + 1) * half {
+                    let u = columns[t][j];
+                    let v = columns[t][j + half] * s % Q;
+                    columns[t][j] = (u + v) % Q;
+                    columns[t][j + half] = (u + Q - v) % Q;
+                    j += 1;
+                }
+                i += 1;
+            }
+            l += 1;
+        }
+        t += 1;
+    }
+
+    // each sum adds one column to the sum without the lowest bit
+    let mut table = [[[0u16; BLOCKS]; 256]; BYTES];
+    let mut k = 0;
+    while k < BYTES {
+        let mut bits: usize = 1;
+        while bits < 256 {
+            let column = columns[8 * k + bits.trailing_zeros() as usize];
+            let mut b = 0;
+            while b < BLOCKS {
+                let sum = table[k][bits & (bits - 1)][b] as u32 + column[b];
+                table[k][bits][b] = (sum % Q) as u16;
+                b += 1;
+            }
+            bits += 1;
+        }
+        k += 1;
+    }
+    table
+}
+
+impl SmallNTTPoly {
+    /// The NTT form of a polynomial whose coefficients are all 0 or 1, or
+    /// `None` if some coefficient is not. Equal to the conversion with
+    /// `hvc_ntt`, but the first layers use the precomputed subset sums.
+    pub(crate) fn from_binary<T: Copy + Into<i64>>(coeffs: &[T; N]) -> Option<Self> {
+        let mut p = [0u16; N];
+        for r in 0..STRIDE {
+            let mut bits = 0;
+            for t in 0..BLOCKS {
+                let e: i64 = coeffs[t * STRIDE + r].into();
+                if e as u64 > 1 {
+                    return None;
+                }
+                bits |= (e as usize) << t;
+            }
+            for b in 0..BLOCKS {
+                let sum: u32 = (0..BYTES)
+                    .map(|k| TABLE[k][(bits >> (8 * k)) & 0xff][b] as u32)
+                    .sum();
+                p[b * STRIDE + r] = (sum % Q) as u16;
+            }
+        }
+
+        // the remaining layers of `hvc_ntt`
+        let mut t = STRIDE;
+        for l in LAYERS..N.trailing_zeros() as usize {
+            let ht = t >> 1;
+            for i in 0..1 << l {
+                let s = HVC_NTT_TABLE[(1 << l) + i] as u32;
+                for j in i * t..i * t + ht {
+                    let u = p[j] as u32;
+                    let v = p[j + ht] as u32 * s % Q;
+                    p[j] = ((u + v) % Q) as u16;
+                    p[j + ht] = ((u + Q - v) % Q) as u16;
+                }
+            }
+            t = ht;
+        }
+        Some(Self { coeffs: p })
+    }
+}
+
+/// `sum_i bases[i] * inputs[i]` in the NTT domain, with a single reduction of
+/// the sum of the products.
+pub(crate) fn fixed_base_sum(
+    bases: &[SmallNTTPoly],
+    inputs: impl IntoIterator<Item = SmallNTTPoly>,
+) -> SmallNTTPoly {
+    // each product is below 2^28, so the sum of up to 2^36 of them fits
+    let mut acc = [0u64; N];
+    for (base, input) in bases.iter().zip(inputs) {
+        for (a, (&h, &x)) in acc
+            .iter_mut()
+            .zip(base.coeffs.iter().zip(input.coeffs.iter()))
+        {
+            *a += h as u64 * x as u64;
+        }
+    }
+    let mut res = SmallNTTPoly::default();
+    for (e, a) in res.coeffs.iter_mut().zip(acc) {
+        *e = (a % Q as u64) as u16;
+    }
+    res
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::poly::SmallPoly;
+    use crate::SignedPoly;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    #[test]
+    fn test_from_binary() {
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+        let mut inputs: Vec<SignedPoly> =
+            (0..10).map(|_| SignedPoly::rand_binary(&mut rng)).collect();
+        inputs.push(SignedPoly::default());
+        inputs.push(SignedPoly { coeffs: [1; N] });
+        for poly in inputs.iter() {
+            let expected = SmallNTTPoly::from(poly);
+            assert_eq!(SmallNTTPoly::from_binary(&poly.coeffs), Some(expected));
+            let small = SmallPoly::from(poly);
+            assert_eq!(SmallNTTPoly::from_binary(&small.coeffs), Some(expected));
+        }
+
+        // other coefficients are refused
+        let mut poly = inputs[0];
+        for e in [-1, 2] {
+            poly.coeffs[N - 1] = e;
+            assert_eq!(SmallNTTPoly::from_binary(&poly.coeffs), None);
+        }
+    }
+}
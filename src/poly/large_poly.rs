@@ -1,6 +1,9 @@
 use super::LargeNTTPoly;
 use super::LargePoly;
 use super::SignedPoly;
+use crate::codec::{Decode, Encode, Reader};
+use crate::ct;
+use crate::error::{Error, Result};
 use crate::LARGE_MODULUS as MODULUS;
 use crate::LARGE_MODULUS_BITS;
 use crate::LARGE_SAMPLE_THRESHOLD;
@@ -70,7 +73,7 @@ impl AddAssign for LargePoly {
         self.coeffs
             .iter_mut()
             .zip(other.coeffs)
-            .for_each(|(x, y)| *x = (*x + y) % MODULUS)
+            .for_each(|(x, y)| *x = reduce(*x as u64 + y as u64))
     }
 }
 
@@ -130,7 +133,7 @@ impl LargePoly {
         let mut res = [SignedPoly::default(); LARGE_MODULUS_BITS];
         let mut base_coeffs = self.coeffs;
         for poly in res.iter_mut() {
-            for (tar_coeff, cur_coeff) in (*poly).coeffs.iter_mut().zip(base_coeffs.iter_mut()) {
+            for (tar_coeff, cur_coeff) in poly.coeffs.iter_mut().zip(base_coeffs.iter_mut()) {
                 *tar_coeff = ((*cur_coeff) & 1) as i32;
                 (*cur_coeff) >>= 1;
             }
@@ -151,6 +154,15 @@ impl LargePoly {
         (&res).into()
     }
 
+    /// the largest absolute value of the coefficients, lifted to [-(q-1)/2, (q-1)/2]
+    pub fn infinity_norm(&self) -> u32 {
+        self.coeffs
+            .iter()
+            .map(|&e| if e > MODULUS / 2 { MODULUS - e } else { e })
+            .max()
+            .unwrap_or_default()
+    }
+
     /// A 256 digest of the polynomial
     pub(crate) fn digest(&self) -> [u8; 32] {
         let mut inputs = Vec::new();
@@ -182,9 +194,7 @@ impl From<&LargePoly> for LargeNTTPoly {
     // convert poly into its ntt form. Requires that coefficients are between 0 and 12289
     fn from(poly: &LargePoly) -> Self {
         let mut coeffs = poly.coeffs;
-        unsafe {
-            hots_ntt(coeffs.as_mut_ptr());
-        }
+        hots_ntt(&mut coeffs);
         Self { coeffs }
     }
 }
@@ -192,9 +202,7 @@ impl From<&LargePoly> for LargeNTTPoly {
 impl From<&LargeNTTPoly> for LargePoly {
     fn from(poly: &LargeNTTPoly) -> Self {
         let mut coeffs = poly.coeffs;
-        unsafe {
-            hots_inv_ntt(coeffs.as_mut_ptr());
-        }
+        hots_inv_ntt(&mut coeffs);
         Self { coeffs }
     }
 }
@@ -209,7 +217,7 @@ impl Add for LargeNTTPoly {
             .iter_mut()
             .zip(self.coeffs.iter().zip(other.coeffs.iter()))
         {
-            *e = (f + g) % MODULUS
+            *e = reduce(*f as u64 + *g as u64)
         }
 
         res
@@ -219,7 +227,7 @@ impl Add for LargeNTTPoly {
 impl AddAssign for LargeNTTPoly {
     fn add_assign(&mut self, other: LargeNTTPoly) {
         for (x, y) in self.coeffs.iter_mut().zip(other.coeffs) {
-            *x = (*x + y) % MODULUS
+            *x = reduce(*x as u64 + y as u64)
         }
     }
 }
@@ -235,16 +243,62 @@ impl Mul for LargeNTTPoly {
             .iter_mut()
             .zip(self.coeffs.iter().zip(other.coeffs.iter()))
         {
-            *e = (((*f as u64) * (*g as u64)) % MODULUS as u64) as u32
+            *e = reduce(*f as u64 * *g as u64)
         }
 
         res
     }
 }
 
-// #[inline]
+impl Encode for LargePoly {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.coeffs
+            .iter()
+            .for_each(|x| out.extend_from_slice(&x.to_le_bytes()))
+    }
+}
+
+impl Decode for LargePoly {
+    fn decode_into(&mut self, reader: &mut Reader) -> Result<()> {
+        for e in self.coeffs.iter_mut() {
+            *e = reader.read_u32()?;
+            if *e >= MODULUS {
+                return Err(Error::InvalidCoefficient(*e as i64));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Encode for LargeNTTPoly {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.coeffs
+            .iter()
+            .for_each(|x| out.extend_from_slice(&x.to_le_bytes()))
+    }
+}
+
+impl Decode for LargeNTTPoly {
+    fn decode_into(&mut self, reader: &mut Reader) -> Result<()> {
+        for e in self.coeffs.iter_mut() {
+            *e = reader.read_u32()?;
+            if *e >= MODULUS {
+                return Err(Error::InvalidCoefficient(*e as i64));
+            }
+        }
+        Ok(())
+    }
+}
+
+// The arithmetic also runs on secret keys, so it is constant time.
+#[inline]
+fn reduce(a: u64) -> u32 {
+    ct::rem(a, MODULUS as u64) as u32
+}
+
+#[inline]
 fn lift(a: i64) -> u32 {
-    (a % MODULUS as i64 + MODULUS as i64) as u32 % MODULUS
+    ct::lift(a, MODULUS as u64) as u32
 }
 
 #[cfg(test)]
@@ -1,7 +1,11 @@
+mod fixed_base;
 mod large_poly;
 mod signed_poly;
 mod small_poly;
 
+pub(crate) use fixed_base::fixed_base_sum;
+pub use fixed_base::HashMode;
+
 use crate::{ALPHA, N};
 
 #[derive(Debug, Clone, PartialEq, Copy)]
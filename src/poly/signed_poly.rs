@@ -1,6 +1,9 @@
 use super::LargePoly;
 use super::SmallPoly;
 use super::TerPolyCoeffEncoding;
+use crate::codec::{Decode, Encode, Reader};
+use crate::ct;
+use crate::error::Result;
 use crate::SignedPoly;
 use crate::ALPHA;
 use crate::BETA_S;
@@ -20,6 +23,7 @@ use sha2::Sha256;
 use std::fmt::Display;
 use std::ops::Add;
 use std::ops::AddAssign;
+use zeroize::Zeroize;
 
 impl Default for SignedPoly {
     fn default() -> Self {
@@ -110,18 +114,16 @@ impl SignedPoly {
 
         let mut res = Self::default();
         let mut tmp = [0i8; N];
-        let mut buf = [0u8; 2 * N];
-        let bin: Vec<i8> = bin.coeffs.iter().map(|&x| x as i8).collect();
-        let ter: Vec<u8> = ter.indices.iter().map(|&x| x as u8).collect();
-
-        unsafe {
-            ternary_mul(
-                tmp.as_mut_ptr(),
-                buf.as_mut_ptr(),
-                bin.as_ptr(),
-                ter.as_ptr(),
-            );
-        }
+        let mut a = [0i8; N];
+        let mut b = [0u8; ALPHA];
+        a.iter_mut()
+            .zip(bin.coeffs.iter())
+            .for_each(|(e, &x)| *e = x as i8);
+        b.iter_mut()
+            .zip(ter.indices.iter())
+            .for_each(|(e, &x)| *e = x as u8);
+
+        ternary_mul(&mut tmp, &a, &b);
         for (e, f) in res.coeffs.iter_mut().zip(tmp.iter()) {
             *e = *f as i32
         }
@@ -184,7 +186,19 @@ impl SignedPoly {
         true
     }
 
+    /// the largest absolute value of the coefficients
+    pub fn infinity_norm(&self) -> u32 {
+        self.coeffs
+            .iter()
+            .map(|e| e.unsigned_abs())
+            .max()
+            .unwrap_or_default()
+    }
+
     /// sample a random polynomial with coefficients between [-beta_s, beta_s]
+    ///
+    /// Constant time: the rejected draws are independent of the accepted ones,
+    /// so the number of draws does not depend on the coefficients.
     pub fn rand_mod_beta_s<R: Rng>(rng: &mut R) -> Self {
         // todo: improve sampling rates
         let mut res = Self::default();
@@ -194,7 +208,7 @@ impl SignedPoly {
                 tmp = rng.next_u32();
             }
 
-            *e = (tmp % TWO_BETA_S_PLUS_ONE) as i32 - BETA_S as i32;
+            *e = ct::rem(tmp as u64, TWO_BETA_S_PLUS_ONE as u64) as i32 - BETA_S as i32;
         }
 
         res
@@ -238,37 +252,30 @@ impl SignedPoly {
         Self { coeffs }
     }
 
-    // sample a random binary polynomial with a fixed weight
+    /// sample a random ternary polynomial with `weight` non-zero coefficients
+    ///
+    /// Constant time: the first `weight` of N random keys are tagged with a
+    /// random sign and the others with zero, and the keys are shuffled with a
+    /// sorting network, so that the positions of the non-zero coefficients are
+    /// never branched on or used as indices.
     pub fn rand_fixed_weight_ternary<R: Rng>(rng: &mut R, weight: usize) -> Self {
         #[cfg(debug_assertions)]
-        assert!(weight <= 64);
+        assert!(weight <= N);
+
+        // the two low bits of a key are its tag: 0 for 0, 1 for 1 and 3 for -1
+        let mut keys = [0u64; N];
+        for (i, key) in keys.iter_mut().enumerate() {
+            let r = rng.next_u64() >> 1;
+            let tag = if i < weight { 1 | (r & 2) } else { 0 };
+            *key = (r & !3) | tag;
+        }
+        ct::sort(&mut keys);
 
-        let mut ct = 0;
         let mut coeffs = [0; N];
-        let mut tmp_ct = 0;
-        let mut tmp = rng.next_u32();
-        let mut sign = rng.next_u64();
-
-        while ct < weight {
-            let index = (tmp & 0xFF) as usize;
-            tmp >>= 9;
-            tmp_ct += 1;
-            if tmp_ct == 3 {
-                tmp = rng.next_u32();
-                tmp_ct = 0;
-            }
-
-            if coeffs[index] == 0 {
-                ct += 1;
-                if sign & 1 == 0 {
-                    coeffs[index] = 1
-                } else {
-                    coeffs[index] = -1
-                }
-                sign >>= 1;
-            }
+        for (e, key) in coeffs.iter_mut().zip(keys.iter_mut()) {
+            *e = (*key & 1) as i32 * (1 - (*key & 2) as i32);
         }
-
+        keys.zeroize();
         Self { coeffs }
     }
 
@@ -313,6 +320,25 @@ impl SignedPoly {
     }
 }
 
+impl Encode for SignedPoly {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.coeffs
+            .iter()
+            .for_each(|x| out.extend_from_slice(&x.to_le_bytes()))
+    }
+}
+
+impl Decode for SignedPoly {
+    // the range of the coefficients depends on the context
+    // and is checked by the caller
+    fn decode_into(&mut self, reader: &mut Reader) -> Result<()> {
+        for e in self.coeffs.iter_mut() {
+            *e = reader.read_i32()?;
+        }
+        Ok(())
+    }
+}
+
 impl From<&SignedPoly> for TerPolyCoeffEncoding {
     fn from(poly: &SignedPoly) -> Self {
         // TODO: this conversion should only be possible if poly has same number of
@@ -365,4 +391,26 @@ mod test {
             assert_eq!(prod_1.lifted_large(), prod_4)
         }
     }
+
+    #[test]
+    fn test_fixed_weight_ternary() {
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+        for weight in [0, 1, BETA_S, N] {
+            let poly = SignedPoly::rand_fixed_weight_ternary(&mut rng, weight);
+            assert!(poly.is_ternary());
+            assert_eq!(poly.coeffs.iter().filter(|&&e| e != 0).count(), weight);
+        }
+        let full = SignedPoly::rand_fixed_weight_ternary(&mut rng, N);
+        assert!(full.coeffs.contains(&1) && full.coeffs.contains(&-1));
+
+        // the non-zero coefficients are spread over all positions
+        let mut hits = [0; N];
+        for _ in 0..200 {
+            let poly = SignedPoly::rand_fixed_weight_ternary(&mut rng, BETA_S);
+            for (h, &e) in hits.iter_mut().zip(poly.coeffs.iter()) {
+                *h += e.abs();
+            }
+        }
+        assert!(hits.iter().all(|&h| h > 0));
+    }
 }
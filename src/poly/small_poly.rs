@@ -7,6 +7,8 @@ use sha2::Sha256;
 use super::SignedPoly;
 use super::SmallNTTPoly;
 use super::SmallPoly;
+use crate::codec::{impl_serialization, Decode, Encode, Reader};
+use crate::error::{Error, Result};
 use crate::N;
 use crate::SMALL_MODULUS as MODULUS;
 use crate::SMALL_MODULUS_BITS;
@@ -47,7 +49,7 @@ impl From<&SignedPoly> for SmallPoly {
         res.coeffs
             .iter_mut()
             .zip(input.coeffs)
-            .for_each(|(e, f)| *e = lift(f) as u16);
+            .for_each(|(e, f)| *e = lift(f));
 
         res
     }
@@ -130,7 +132,7 @@ impl SmallPoly {
         let mut res = [SignedPoly::default(); SMALL_MODULUS_BITS];
         let mut base_coeffs = self.coeffs;
         for poly in res.iter_mut() {
-            for (tar_coeff, cur_coeff) in (*poly).coeffs.iter_mut().zip(base_coeffs.iter_mut()) {
+            for (tar_coeff, cur_coeff) in poly.coeffs.iter_mut().zip(base_coeffs.iter_mut()) {
                 *tar_coeff = ((*cur_coeff) & 1) as i32;
                 (*cur_coeff) >>= 1;
             }
@@ -182,9 +184,7 @@ impl From<&SmallPoly> for SmallNTTPoly {
     // convert poly into its ntt form. Requires that coefficients are between 0 and 12289
     fn from(poly: &SmallPoly) -> Self {
         let mut coeffs = poly.coeffs;
-        unsafe {
-            hvc_ntt(coeffs.as_mut_ptr());
-        }
+        hvc_ntt(&mut coeffs);
         Self { coeffs }
     }
 }
@@ -192,9 +192,7 @@ impl From<&SmallPoly> for SmallNTTPoly {
 impl From<&SmallNTTPoly> for SmallPoly {
     fn from(poly: &SmallNTTPoly) -> Self {
         let mut coeffs = poly.coeffs;
-        unsafe {
-            hvc_inv_ntt(coeffs.as_mut_ptr());
-        }
+        hvc_inv_ntt(&mut coeffs);
         Self { coeffs }
     }
 }
@@ -242,6 +240,48 @@ impl Mul for SmallNTTPoly {
     }
 }
 
+impl Encode for SmallPoly {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.coeffs
+            .iter()
+            .for_each(|x| out.extend_from_slice(&x.to_le_bytes()))
+    }
+}
+
+impl Decode for SmallPoly {
+    fn decode_into(&mut self, reader: &mut Reader) -> Result<()> {
+        for e in self.coeffs.iter_mut() {
+            *e = reader.read_u16()?;
+            if *e >= MODULUS {
+                return Err(Error::InvalidCoefficient(*e as i64));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl_serialization!(SmallPoly);
+
+impl Encode for SmallNTTPoly {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.coeffs
+            .iter()
+            .for_each(|x| out.extend_from_slice(&x.to_le_bytes()))
+    }
+}
+
+impl Decode for SmallNTTPoly {
+    fn decode_into(&mut self, reader: &mut Reader) -> Result<()> {
+        for e in self.coeffs.iter_mut() {
+            *e = reader.read_u16()?;
+            if *e >= MODULUS {
+                return Err(Error::InvalidCoefficient(*e as i64));
+            }
+        }
+        Ok(())
+    }
+}
+
 fn lift(a: i32) -> u16 {
     (a % MODULUS as i32 + MODULUS as i32) as u16 % MODULUS
 }
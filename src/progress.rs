@@ -0,0 +1,59 @@
+//! Progress reports of long computations, such as key generation.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// `done` of the `total` items of `stage` are finished.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+    pub stage: Stage,
+    pub done: usize,
+    pub total: usize,
+}
+
+/// The stages of key generation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+    /// the one-time public keys and their digests, i.e., the leaves
+    Leaves,
+    /// the non-leaf nodes of the tree
+    Nodes,
+}
+
+/// The number of items processed by a task between two reports.
+pub(crate) const CHUNK_SIZE: usize = 64;
+
+/// A callback for progress reports. With the `parallel` feature it is called
+/// from several threads, so the reports may arrive out of order.
+pub type ProgressCallback<'a> = &'a (dyn Fn(Progress) + Sync);
+
+/// The callback that ignores the reports.
+pub(crate) fn no_progress(_: Progress) {}
+
+/// Counts the finished items of a stage and reports them.
+pub(crate) struct Counter<'a> {
+    stage: Stage,
+    total: usize,
+    done: AtomicUsize,
+    callback: ProgressCallback<'a>,
+}
+
+impl<'a> Counter<'a> {
+    pub(crate) fn new(stage: Stage, total: usize, callback: ProgressCallback<'a>) -> Self {
+        Self {
+            stage,
+            total,
+            done: AtomicUsize::new(0),
+            callback,
+        }
+    }
+
+    /// Record that `count` more items are finished.
+    pub(crate) fn add(&self, count: usize) {
+        let done = self.done.fetch_add(count, Ordering::Relaxed) + count;
+        (self.callback)(Progress {
+            stage: self.stage,
+            done,
+            total: self.total,
+        })
+    }
+}
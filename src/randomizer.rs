@@ -20,6 +20,8 @@ impl Randomizers {
         }
     }
 
+    /// Randomizers derived from the roots alone, so that a fixed set of signers
+    /// gets the same randomizers for every message and index.
     pub fn from_pks(roots: &[SmallPoly]) -> Self {
         // hash the roots into randomizers
         let mut input = Vec::new();
@@ -32,4 +34,83 @@ impl Randomizers {
         let mut rng = ChaCha20Rng::from_seed(seed.into());
         Self::rand(&mut rng, roots.len())
     }
+
+    /// Randomizers bound to the signed message and index as well as the roots.
+    /// `domain` separates the schemes, and versions of a scheme, using them.
+    pub fn from_pks_and_message(
+        domain: &[u8],
+        roots: &[SmallPoly],
+        message: &[u8],
+        index: usize,
+    ) -> Self {
+        let mut hasher = Sha256::new();
+        for input in [domain, message] {
+            hasher.update((input.len() as u64).to_le_bytes());
+            hasher.update(input);
+        }
+        hasher.update((index as u64).to_le_bytes());
+        hasher.update((roots.len() as u64).to_le_bytes());
+        for e in roots {
+            hasher.update(e.digest());
+        }
+        let seed = hasher.finalize();
+        let mut rng = ChaCha20Rng::from_seed(seed.into());
+        Self::rand(&mut rng, roots.len())
+    }
+
+    /// Randomizers of the members of `committee` at the positions in `signers`,
+    /// given in increasing order; every position must be in the committee.
+    ///
+    /// These are the randomizers of [`Randomizers::from_pks_and_message`] for
+    /// the whole committee, restricted to the signers. Each of them thus depends
+    /// on every root of the committee, including those of the members that did
+    /// not sign, and a rogue key cannot be chosen after the randomizers of the
+    /// others are known. Members get the same randomizer whatever the subset.
+    pub fn from_committee(
+        domain: &[u8],
+        committee: &[SmallPoly],
+        signers: impl IntoIterator<Item = usize>,
+        message: &[u8],
+        index: usize,
+    ) -> Self {
+        Self::from_pks_and_message(domain, committee, message, index).select(signers)
+    }
+
+    /// the randomizers at the positions in `signers`
+    pub(crate) fn select(&self, signers: impl IntoIterator<Item = usize>) -> Self {
+        Self {
+            poly: signers.into_iter().map(|i| self.poly[i]).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const DOMAIN: &[u8] = b"domain";
+
+    #[test]
+    fn test_binding() {
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+        let roots: Vec<SmallPoly> = (0..4).map(|_| SmallPoly::rand_poly(&mut rng)).collect();
+
+        let reference = Randomizers::from_pks_and_message(DOMAIN, &roots, b"message", 0);
+        assert_eq!(reference.poly.len(), roots.len());
+        assert_eq!(
+            Randomizers::from_pks_and_message(DOMAIN, &roots, b"message", 0),
+            reference
+        );
+        for other in [
+            Randomizers::from_pks_and_message(DOMAIN, &roots, b"message", 1),
+            Randomizers::from_pks_and_message(DOMAIN, &roots, b"other", 0),
+            Randomizers::from_pks_and_message(b"other", &roots, b"message", 0),
+            Randomizers::from_pks_and_message(DOMAIN, &roots[1..], b"message", 0),
+            // inputs are length prefixed, so they cannot be shifted into each other
+            Randomizers::from_pks_and_message(b"domainm", &roots, b"essage", 0),
+            Randomizers::from_pks(&roots),
+        ] {
+            assert_ne!(other.poly[..3], reference.poly[..3]);
+        }
+    }
 }
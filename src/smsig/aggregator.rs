@@ -0,0 +1,259 @@
+use super::{
+    SMSigPK, SMSigParam, SMSigScheme, SMSignature, SignerBitmap, SubsetSignature, SCHEME_ID,
+};
+use crate::{
+    error::{Error, Result},
+    randomizer::Randomizers,
+    MultiSig,
+};
+
+/// Aggregates the signatures of a committee as they arrive.
+///
+/// The randomizers are derived from the whole committee, so each signature is
+/// randomized and accumulated as soon as it is added, in any order. Member `i`
+/// gets the same randomizer as in [`MultiSig::aggregate`] over the whole
+/// committee, hence an aggregate of every member verifies with
+/// [`MultiSig::batch_verify`]. Partial aggregates verify with
+/// [`SubsetSignature::verify`], see [`Aggregator::finalize_subset`].
+///
+/// Signatures are not verified by [`Aggregator::add`]; use
+/// [`Aggregator::add_verified`] to reject the invalid ones before they spoil the
+/// aggregate, or [`SMSigScheme::find_invalid`] to locate them afterwards.
+///
+/// [`MultiSig::aggregate`]: crate::MultiSig::aggregate
+/// [`MultiSig::batch_verify`]: crate::MultiSig::batch_verify
+#[derive(Debug, Clone)]
+pub struct Aggregator {
+    committee: Vec<SMSigPK>,
+    message: Vec<u8>,
+    index: usize,
+    randomizers: Randomizers,
+    included: SignerBitmap,
+    aggregate: Option<Box<SMSignature>>,
+}
+
+impl Aggregator {
+    /// Start aggregating the signatures of `message` at `index` by the members
+    /// of `committee`.
+    pub fn new(committee: &[SMSigPK], message: &[u8], index: usize) -> Self {
+        Self {
+            committee: committee.to_vec(),
+            message: message.to_vec(),
+            index,
+            randomizers: Randomizers::from_pks_and_message(SCHEME_ID, committee, message, index),
+            included: SignerBitmap::new(committee.len()),
+            aggregate: None,
+        }
+    }
+
+    /// Randomize the signature of the committee member at position `signer`
+    /// and add it to the aggregate.
+    pub fn add(&mut self, signer: usize, sig: &SMSignature) -> Result<()> {
+        if signer >= self.included.len() {
+            return Err(Error::UnknownSigner(signer));
+        }
+        if self.included.contains(signer) {
+            return Err(Error::DuplicateSigner(signer));
+        }
+        if sig.path.index != self.index {
+            return Err(Error::MixedIndices(self.index, sig.path.index));
+        }
+        if let Some(aggregate) = &self.aggregate {
+            if sig.path.nodes.len() != aggregate.path.nodes.len() {
+                return Err(Error::InvalidPathLength(sig.path.nodes.len()));
+            }
+        }
+
+        let mut sig = Box::new(sig.clone());
+        sig.randomize_with(&self.randomizers.poly[signer])?;
+        match &mut self.aggregate {
+            None => self.aggregate = Some(sig),
+            Some(aggregate) => aggregate.add_randomized(&sig),
+        }
+        self.included.insert(signer)
+    }
+
+    /// Verify the signature of the member at position `signer` with
+    /// [`MultiSig::verify`], and add it to the aggregate if it is valid.
+    /// Fails with [`Error::InvalidSignature`] otherwise.
+    ///
+    /// [`MultiSig::verify`]: crate::MultiSig::verify
+    pub fn add_verified(
+        &mut self,
+        signer: usize,
+        sig: &SMSignature,
+        pp: &SMSigParam,
+    ) -> Result<()> {
+        let pk = self
+            .committee
+            .get(signer)
+            .ok_or(Error::UnknownSigner(signer))?;
+        if sig.is_aggregated() || !SMSigScheme::verify(pk, &self.message, sig, pp) {
+            return Err(Error::InvalidSignature(signer));
+        }
+        self.add(signer, sig)
+    }
+
+    /// Add the aggregate of other members of the committee, e.g. made by
+    /// another aggregator for the same message and index. None of its signers
+    /// may be included yet.
+    pub fn merge(&mut self, sig: &SubsetSignature) -> Result<()> {
+        let included = self.included.union(sig.signers())?;
+        match &mut self.aggregate {
+            None => {
+                let aggregate = sig.signature();
+                if !aggregate.is_aggregated() {
+                    return Err(Error::NotRandomized);
+                }
+                if aggregate.index() != self.index {
+                    return Err(Error::MixedIndices(self.index, aggregate.index()));
+                }
+                self.aggregate = Some(Box::new(aggregate.clone()));
+            }
+            Some(aggregate) => aggregate.merge(sig.signature())?,
+        }
+        self.included = included;
+        Ok(())
+    }
+
+    /// The index of the one-time keys being aggregated.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The number of members in the committee.
+    pub fn committee_size(&self) -> usize {
+        self.committee.len()
+    }
+
+    /// The number of signatures added so far.
+    pub fn num_signers(&self) -> usize {
+        self.included.count()
+    }
+
+    /// Whether the member at position `signer` is included.
+    pub fn is_included(&self, signer: usize) -> bool {
+        self.included.contains(signer)
+    }
+
+    /// The positions of the included members, in committee order.
+    pub fn signers(&self) -> Vec<usize> {
+        self.included.iter().collect()
+    }
+
+    /// The public keys of the included members, in committee order.
+    pub fn signer_pks(&self) -> Vec<SMSigPK> {
+        self.included.iter().map(|i| self.committee[i]).collect()
+    }
+
+    /// The randomizers of the included members, in committee order.
+    pub fn randomizers(&self) -> Randomizers {
+        self.randomizers.select(self.included.iter())
+    }
+
+    /// The aggregate of the signatures added so far.
+    pub fn finalize(&self) -> Result<SMSignature> {
+        self.aggregate.as_deref().cloned().ok_or(Error::EmptyInput)
+    }
+
+    /// The aggregate of the signatures added so far, with the bitmap of the
+    /// members it covers.
+    pub fn finalize_subset(&self) -> Result<SubsetSignature> {
+        Ok(SubsetSignature::new(
+            self.included.clone(),
+            self.finalize()?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::smsig::test::with_large_stack;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    const MESSAGE: &[u8] = b"message";
+
+    fn assert_partial_aggregate(aggregator: &Aggregator, pp: &SMSigParam) {
+        let sig = aggregator.finalize().unwrap();
+        assert!(SMSigScheme::batch_verify_with_randomizers(
+            &aggregator.signer_pks(),
+            MESSAGE,
+            &sig,
+            &aggregator.randomizers(),
+            pp
+        ));
+        assert!(!SMSigScheme::batch_verify_with_randomizers(
+            &aggregator.signer_pks(),
+            b"another message",
+            &sig,
+            &aggregator.randomizers(),
+            pp
+        ));
+    }
+
+    fn add_error(aggregator: &mut Aggregator, signer: usize, sig: &SMSignature) -> Error {
+        aggregator.add(signer, sig).unwrap_err()
+    }
+
+    #[test]
+    fn test_aggregator() {
+        with_large_stack(|| {
+            let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+            let pp = SMSigParam::setup_with_height(&mut rng, 3).unwrap();
+            let index = 2;
+            let (pks, sigs): (Vec<SMSigPK>, Vec<SMSignature>) = (0..4u8)
+                .map(|i| {
+                    let (pk, sk) = SMSigScheme::key_gen(&[i; 32], &pp);
+                    (pk, SMSigScheme::sign(&sk, index, MESSAGE, &pp).unwrap())
+                })
+                .unzip();
+
+            let mut aggregator = Aggregator::new(&pks, MESSAGE, index);
+            assert_eq!(aggregator.finalize().unwrap_err(), Error::EmptyInput);
+
+            // signatures arrive in any order
+            for (count, signer) in [3, 0, 2].into_iter().enumerate() {
+                aggregator.add(signer, &sigs[signer]).unwrap();
+                assert_eq!(aggregator.num_signers(), count + 1);
+                assert_partial_aggregate(&aggregator, &pp);
+            }
+            assert_eq!(aggregator.signers(), vec![0, 2, 3]);
+            assert!(!aggregator.is_included(1));
+            assert_eq!(aggregator.signer_pks(), vec![pks[0], pks[2], pks[3]]);
+
+            assert_eq!(
+                add_error(&mut aggregator, 2, &sigs[2]),
+                Error::DuplicateSigner(2)
+            );
+            assert_eq!(
+                add_error(&mut aggregator, 4, &sigs[1]),
+                Error::UnknownSigner(4)
+            );
+            let (_, sk) = SMSigScheme::key_gen(&[1; 32], &pp);
+            let other_index = SMSigScheme::sign(&sk, 0, MESSAGE, &pp).unwrap();
+            assert_eq!(
+                add_error(&mut aggregator, 1, &other_index),
+                Error::MixedIndices(index, 0)
+            );
+            let aggregated = aggregator.finalize().unwrap();
+            assert_eq!(
+                add_error(&mut aggregator, 1, &aggregated),
+                Error::AlreadyRandomized
+            );
+            assert_eq!(aggregator.signers(), vec![0, 2, 3]);
+
+            // with every member, this is the aggregate of the whole committee
+            aggregator.add(1, &sigs[1]).unwrap();
+            let sig = aggregator.finalize().unwrap();
+            assert_eq!(
+                sig.to_bytes(),
+                SMSigScheme::aggregate(&sigs, &pks, MESSAGE)
+                    .unwrap()
+                    .to_bytes()
+            );
+            assert!(SMSigScheme::batch_verify(&pks, MESSAGE, &sig, &pp));
+        });
+    }
+}
@@ -0,0 +1,352 @@
+//! Verification of many aggregated signatures at once.
+
+use super::{SMSigPK, SMSigParam, SMSigScheme, SMSignature, SCHEME_ID};
+use crate::{
+    beta_agg, beta_sigma_agg,
+    hots::HotsPK,
+    param::SECURITY_BITS,
+    poly::{LargeNTTPoly, LargePoly, SmallPoly},
+    randomizer::Randomizers,
+    MultiSig, SignedPoly, GAMMA, LARGE_MODULUS, LARGE_MODULUS_BITS, N, SMALL_MODULUS,
+    SMALL_MODULUS_BITS,
+};
+use rand::Rng;
+
+const LARGE_Q: i64 = LARGE_MODULUS as i64;
+const SMALL_Q: i64 = SMALL_MODULUS as i64;
+
+/// An aggregated signature of `message` by `committee`, as checked by
+/// [`MultiSig::batch_verify`].
+#[derive(Debug, Clone, Copy)]
+pub struct AggregateItem<'a> {
+    pub committee: &'a [SMSigPK],
+    pub message: &'a [u8],
+    pub signature: &'a SMSignature,
+}
+
+impl SMSigScheme {
+    /// The positions of the `items` that fail [`MultiSig::batch_verify`], in
+    /// increasing order.
+    ///
+    /// The HOTS equations of all items are checked through random linear
+    /// combinations, so that the products with `HotsParam::a` are computed
+    /// once per round rather than once per item. The same holds for the
+    /// `HVCHash` and `HOTSHash` evaluations of the paths, and for the roots
+    /// of committees shared by several items. An invalid batch passes a round
+    /// with probability `1/q`; the number of rounds is chosen so that it is
+    /// accepted with probability below `2^-128`. The coefficients are drawn
+    /// from `rng`, which must not be predictable by the signers. When the
+    /// combination fails, each item is verified on its own.
+    pub fn verify_aggregates<R: Rng>(
+        items: &[AggregateItem],
+        pp: &SMSigParam,
+        rng: &mut R,
+    ) -> Vec<usize> {
+        let (candidates, mut failures): (Vec<usize>, Vec<usize>) =
+            (0..items.len()).partition(|&i| is_well_formed(&items[i], pp));
+        if candidates.is_empty() {
+            return failures;
+        }
+
+        let batch = Batch::new(items, &candidates);
+        if !batch.verify(pp, rng) {
+            failures.extend(candidates.into_iter().filter(|&i| {
+                let item = &items[i];
+                !Self::batch_verify(item.committee, item.message, item.signature, pp)
+            }));
+            failures.sort_unstable();
+        }
+        failures
+    }
+}
+
+/// The cheap checks of [`SMSigScheme::batch_verify_with_randomizers`], done
+/// for each item before the equations are combined.
+fn is_well_formed(item: &AggregateItem, pp: &SMSigParam) -> bool {
+    let sig = item.signature;
+    let norm_bound = beta_agg(item.committee.len(), pp.height);
+    !item.committee.is_empty()
+        && sig.path.nodes.len() == pp.height - 1
+        && sig.hots_pk.infinity_norm() <= norm_bound
+        && sig.path.infinity_norm() <= norm_bound
+        && sig.hots_sig.infinity_norm() <= beta_sigma_agg(item.committee.len())
+}
+
+/// The number of rounds for a soundness error below `2^-SECURITY_BITS`.
+fn num_rounds(modulus: i64) -> usize {
+    (SECURITY_BITS / (modulus as f64).log2()).ceil() as usize
+}
+
+/// The number of items after which the sums are reduced. Each item adds at
+/// most `MAX_HEIGHT` terms of less than `2^46` to a sum, so that it stays far
+/// from overflowing.
+const REDUCTION_INTERVAL: usize = 1 << 10;
+
+/// Add `c * x` to `acc`, without reduction.
+fn mul_add<T: Copy + Into<i64>>(acc: &mut [i64; N], c: i64, x: &[T; N]) {
+    acc.iter_mut()
+        .zip(x.iter())
+        .for_each(|(a, &x)| *a += c * x.into());
+}
+
+fn reduce_all<'a, const Q: i64>(sums: impl IntoIterator<Item = &'a mut [i64; N]>) {
+    sums.into_iter()
+        .for_each(|acc| acc.iter_mut().for_each(|a| *a %= Q));
+}
+
+fn reduce<const Q: i64>(acc: &[i64; N]) -> [i64; N] {
+    acc.map(|a| a.rem_euclid(Q))
+}
+
+fn to_small(acc: &[i64; N]) -> SmallPoly {
+    SmallPoly {
+        coeffs: reduce::<SMALL_Q>(acc).map(|a| a as u16),
+    }
+}
+
+fn to_large(acc: &[i64; N]) -> LargePoly {
+    LargePoly {
+        coeffs: reduce::<LARGE_Q>(acc).map(|a| a as u32),
+    }
+}
+
+/// A well-formed item with the values its equations need.
+struct Prepared<'a> {
+    sig: &'a SMSignature,
+    /// position of the committee in [`Batch::committees`]
+    committee: usize,
+    randomizers: Randomizers,
+    /// `hm * v0 + v1` for the aggregated HOTS public key
+    hots_target: LargeNTTPoly,
+}
+
+struct Batch<'a> {
+    committees: Vec<&'a [SMSigPK]>,
+    items: Vec<Prepared<'a>>,
+}
+
+impl<'a> Batch<'a> {
+    fn new(items: &[AggregateItem<'a>], positions: &[usize]) -> Self {
+        let mut committees: Vec<&[SMSigPK]> = Vec::new();
+        let items = positions
+            .iter()
+            .map(|&i| {
+                let item = items[i];
+                let committee = committees
+                    .iter()
+                    .position(|&c| c == item.committee)
+                    .unwrap_or_else(|| {
+                        committees.push(item.committee);
+                        committees.len() - 1
+                    });
+                let sig = item.signature;
+                let pk = HotsPK::from(&sig.hots_pk);
+                let hm = LargeNTTPoly::from(&SignedPoly::from_hash_message(item.message));
+                Prepared {
+                    sig,
+                    committee,
+                    randomizers: Randomizers::from_pks_and_message(
+                        SCHEME_ID,
+                        item.committee,
+                        item.message,
+                        sig.index(),
+                    ),
+                    hots_target: hm * LargeNTTPoly::from(&pk.v0) + LargeNTTPoly::from(&pk.v1),
+                }
+            })
+            .collect();
+        Self { committees, items }
+    }
+
+    fn verify<R: Rng>(&self, pp: &SMSigParam, rng: &mut R) -> bool {
+        (0..num_rounds(LARGE_Q)).all(|_| self.verify_hots(pp, rng))
+            && (0..num_rounds(SMALL_Q)).all(|_| self.verify_paths(pp, rng))
+    }
+
+    /// One random combination of `sum_i a_i * sigma_i = hm * v0 + v1`.
+    fn verify_hots<R: Rng>(&self, pp: &SMSigParam, rng: &mut R) -> bool {
+        let mut sigma = vec![[0i64; N]; GAMMA];
+        let mut target = [0i64; N];
+        for (i, item) in self.items.iter().enumerate() {
+            let c = rng.gen_range(0..LARGE_Q);
+            for (acc, s) in sigma.iter_mut().zip(item.sig.hots_sig.sigma.iter()) {
+                mul_add(acc, c, &s.coeffs);
+            }
+            mul_add(&mut target, c, &item.hots_target.coeffs);
+            if (i + 1) % REDUCTION_INTERVAL == 0 {
+                reduce_all::<LARGE_Q>(sigma.iter_mut().chain([&mut target]));
+            }
+        }
+
+        let mut left = LargeNTTPoly::default();
+        for (&a, s) in pp.hots_param.a.iter().zip(sigma.iter()) {
+            left += a * LargeNTTPoly::from(&to_large(s));
+        }
+        left.coeffs.map(i64::from) == reduce::<LARGE_Q>(&target)
+    }
+
+    /// One random combination of the hash equations of the paths: each level
+    /// hashes to the on-path node above it or to the aggregated root, and the
+    /// HOTS public key hashes to the leaf.
+    fn verify_paths<R: Rng>(&self, pp: &SMSigParam, rng: &mut R) -> bool {
+        let mut nodes = vec![[0i64; N]; SMALL_MODULUS_BITS << 1];
+        let mut target = [0i64; N];
+        let mut pks = vec![[0i64; N]; LARGE_MODULUS_BITS << 1];
+        let mut leaves = [0i64; N];
+        // the randomizers of each committee member, combined over the items
+        let mut randomizers: Vec<Vec<[i64; N]>> = self
+            .committees
+            .iter()
+            .map(|c| vec![[0i64; N]; c.len()])
+            .collect();
+
+        for (i, item) in self.items.iter().enumerate() {
+            let path = &item.sig.path;
+            let len = path.nodes.len();
+            for (level, (left, right)) in path.nodes.iter().enumerate() {
+                let c = rng.gen_range(0..SMALL_Q);
+                for (acc, x) in nodes.iter_mut().zip(left.iter().chain(right.iter())) {
+                    mul_add(acc, c, &x.coeffs);
+                }
+                if level == 0 {
+                    for (acc, r) in randomizers[item.committee]
+                        .iter_mut()
+                        .zip(item.randomizers.poly.iter())
+                    {
+                        mul_add(acc, c, &r.coeffs);
+                    }
+                } else {
+                    let (parent_left, parent_right) = &path.nodes[level - 1];
+                    let parent = if (path.index >> (len - level)) & 1 == 1 {
+                        parent_right
+                    } else {
+                        parent_left
+                    };
+                    mul_add(&mut target, c, &SmallPoly::projection(parent).coeffs);
+                }
+            }
+
+            let c = rng.gen_range(0..SMALL_Q);
+            let pk = &item.sig.hots_pk;
+            for (acc, x) in pks.iter_mut().zip(pk.v0.iter().chain(pk.v1.iter())) {
+                mul_add(acc, c, &x.coeffs);
+            }
+            let (leaf_left, leaf_right) = &path.nodes[len - 1];
+            let leaf = if path.index & 1 == 1 {
+                leaf_right
+            } else {
+                leaf_left
+            };
+            mul_add(&mut leaves, c, &SmallPoly::projection(leaf).coeffs);
+            if (i + 1) % REDUCTION_INTERVAL == 0 {
+                reduce_all::<SMALL_Q>(
+                    nodes
+                        .iter_mut()
+                        .chain(pks.iter_mut())
+                        .chain(randomizers.iter_mut().flatten())
+                        .chain([&mut target, &mut leaves]),
+                );
+            }
+        }
+
+        // each root is multiplied once, whatever the number of items
+        for (committee, randomizers) in self.committees.iter().zip(randomizers.iter()) {
+            for (&root, r) in committee.iter().zip(randomizers.iter()) {
+                mul_add(&mut target, 1, &(to_small(r) * root).coeffs);
+            }
+        }
+
+        let nodes: Vec<SmallPoly> = nodes.iter().map(to_small).collect();
+        let pks: Vec<SignedPoly> = pks.iter().map(|x| (&to_small(x)).into()).collect();
+        pp.hvc_hasher.hash(&nodes) == to_small(&target)
+            && pp.hots_hasher.hash(&pks) == to_small(&leaves)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::smsig::test::with_large_stack;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    fn items<'a>(
+        committees: &[&'a [SMSigPK]],
+        messages: &'a [Vec<u8>],
+        sigs: &'a [SMSignature],
+    ) -> Vec<AggregateItem<'a>> {
+        committees
+            .iter()
+            .zip(messages.iter())
+            .zip(sigs.iter())
+            .map(|((&committee, message), signature)| AggregateItem {
+                committee,
+                message,
+                signature,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_verify_aggregates() {
+        with_large_stack(|| {
+            let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+            let pp = SMSigParam::setup_with_height(&mut rng, 3).unwrap();
+            let keys: Vec<_> = (0..4u8)
+                .map(|i| SMSigScheme::key_gen(&[i; 32], &pp))
+                .collect();
+            let committee: Vec<SMSigPK> = keys.iter().map(|(pk, _)| *pk).collect();
+
+            // one slot per index, and a smaller committee for slot 1
+            let committees = [
+                &committee[..],
+                &committee,
+                &committee,
+                &committee,
+                &committee[..2],
+            ];
+            let indices = [0, 1, 2, 3, 1];
+            let messages: Vec<Vec<u8>> = indices
+                .iter()
+                .map(|i| format!("slot {}", i).into())
+                .collect();
+            let sigs: Vec<SMSignature> = committees
+                .iter()
+                .zip(indices.iter().zip(messages.iter()))
+                .map(|(committee, (&index, message))| {
+                    let sigs: Vec<SMSignature> = keys[..committee.len()]
+                        .iter()
+                        .map(|(_, sk)| SMSigScheme::sign(sk, index, message, &pp).unwrap())
+                        .collect();
+                    SMSigScheme::aggregate(&sigs, committee, message).unwrap()
+                })
+                .collect();
+
+            let valid = items(&committees, &messages, &sigs);
+            assert!(SMSigScheme::verify_aggregates(&valid, &pp, &mut rng).is_empty());
+            assert!(SMSigScheme::verify_aggregates(&[], &pp, &mut rng).is_empty());
+
+            // swapped messages fail the combined check and are found one by one
+            let mut swapped = messages.clone();
+            swapped.swap(0, 2);
+            let invalid = items(&committees, &swapped, &sigs);
+            let positions: Vec<usize> = (0..invalid.len()).collect();
+            assert!(!Batch::new(&invalid, &positions).verify(&pp, &mut rng));
+            assert_eq!(
+                SMSigScheme::verify_aggregates(&invalid, &pp, &mut rng),
+                vec![0, 2]
+            );
+
+            // the wrong committee, and a malformed path
+            let mut invalid = items(&committees, &messages, &sigs);
+            invalid[3].committee = &committee[1..];
+            let mut truncated = sigs[1].clone();
+            truncated.path.nodes.pop();
+            invalid[1].signature = &truncated;
+            assert_eq!(
+                SMSigScheme::verify_aggregates(&invalid, &pp, &mut rng),
+                vec![1, 3]
+            );
+        });
+    }
+}
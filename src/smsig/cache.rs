@@ -0,0 +1,242 @@
+//! A background cache of the one-time keys of the upcoming indices.
+
+use super::SMSigParam;
+use crate::{
+    error::Result,
+    hots::{HomomorphicOneTimeSignature, HotsPK, HotsParam, HotsSK},
+    HOTS,
+};
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Condvar, Mutex, MutexGuard},
+    thread::{self, JoinHandle},
+};
+use zeroize::Zeroizing;
+
+/// The one-time keys of the next indices, generated by a background thread.
+///
+/// The thread keeps up to `capacity` keys of consecutive indices, starting at
+/// the next index that has not been taken. Taking an index drops the keys of
+/// the indices before it, which are wiped, and the thread then refills the
+/// queue past it.
+pub(crate) struct KeyCache {
+    shared: Arc<Shared>,
+    /// the parameters of the keys
+    param: Arc<HotsParam>,
+    worker: Option<JoinHandle<()>>,
+}
+
+struct Shared {
+    state: Mutex<State>,
+    changed: Condvar,
+}
+
+struct State {
+    keys: VecDeque<(usize, HotsPK, HotsSK)>,
+    capacity: usize,
+    /// the smallest index that can still be taken
+    first: usize,
+    /// the next index to generate
+    next: usize,
+    num_indices: usize,
+    /// the index that the thread is generating
+    pending: Option<usize>,
+    stop: bool,
+}
+
+impl State {
+    /// Whether the thread has nothing to generate.
+    fn is_idle(&self) -> bool {
+        self.keys.len() >= self.capacity || self.next >= self.num_indices
+    }
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, State> {
+        // a panic while holding the lock leaves the queue consistent
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl KeyCache {
+    /// Start a thread that generates the keys of `seed` from `next_index` on,
+    /// below `num_indices`; fails if the thread cannot be spawned.
+    pub(crate) fn new(
+        seed: &[u8; 32],
+        pp: &SMSigParam,
+        next_index: usize,
+        num_indices: usize,
+        capacity: usize,
+    ) -> Result<Self> {
+        let shared = Arc::new(Shared {
+            state: Mutex::new(State {
+                keys: VecDeque::with_capacity(capacity),
+                capacity,
+                first: next_index,
+                next: next_index,
+                num_indices,
+                pending: None,
+                stop: false,
+            }),
+            changed: Condvar::new(),
+        });
+        let seed = Zeroizing::new(*seed);
+        let param = Arc::new(pp.hots_param);
+        let worker = {
+            let (shared, param) = (shared.clone(), param.clone());
+            thread::Builder::new()
+                .name("squirrel key cache".into())
+                .stack_size(16 << 20)
+                .spawn(move || run(&shared, &seed, &param))?
+        };
+        Ok(Self {
+            shared,
+            param,
+            worker: Some(worker),
+        })
+    }
+
+    /// Take the keys of `index` under the parameters `pp`, waiting for them if
+    /// the thread is generating them, or `None` if they are not in the queue or
+    /// belong to other parameters. The keys of the indices before `index` are
+    /// dropped, and the queue continues after it.
+    pub(crate) fn take(&self, index: usize, pp: &SMSigParam) -> Option<(HotsPK, HotsSK)> {
+        if *self.param != pp.hots_param {
+            return None;
+        }
+        let mut state = self.shared.lock();
+        while state.pending == Some(index) {
+            state = self
+                .shared
+                .changed
+                .wait(state)
+                .unwrap_or_else(|e| e.into_inner());
+        }
+        while state.keys.front().is_some_and(|(i, _, _)| *i < index) {
+            state.keys.pop_front();
+        }
+        let keys = match state.keys.front() {
+            Some((i, _, _)) if *i == index => state.keys.pop_front().map(|(_, pk, sk)| (pk, sk)),
+            _ => None,
+        };
+        state.first = state.first.max(index + 1);
+        state.next = state.next.max(state.first);
+        self.shared.changed.notify_all();
+        keys
+    }
+
+    /// The number of keys in the queue.
+    pub(crate) fn len(&self) -> usize {
+        self.shared.lock().keys.len()
+    }
+
+    /// Wait until the thread has nothing left to generate.
+    #[cfg(test)]
+    pub(crate) fn wait_until_idle(&self) {
+        let mut state = self.shared.lock();
+        while state.pending.is_some() || !state.is_idle() {
+            state = self
+                .shared
+                .changed
+                .wait(state)
+                .unwrap_or_else(|e| e.into_inner());
+        }
+    }
+}
+
+/// The loop of the thread: generate the next key whenever the queue has room.
+fn run(shared: &Shared, seed: &[u8; 32], param: &HotsParam) {
+    let mut state = shared.lock();
+    loop {
+        while !state.stop && state.is_idle() {
+            state = shared
+                .changed
+                .wait(state)
+                .unwrap_or_else(|e| e.into_inner());
+        }
+        if state.stop {
+            return;
+        }
+        let index = state.next;
+        state.next += 1;
+        state.pending = Some(index);
+        drop(state);
+
+        let (pk, sk) = HOTS::key_gen(seed, index, param);
+
+        state = shared.lock();
+        state.pending = None;
+        // the index may have been taken or skipped in the meantime
+        if index >= state.first {
+            state.keys.push_back((index, pk, sk));
+        }
+        shared.changed.notify_all();
+    }
+}
+
+impl Drop for KeyCache {
+    fn drop(&mut self) {
+        self.shared.lock().stop = true;
+        self.shared.changed.notify_all();
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{smsig::test::with_large_stack, MultiSig, SMSigParam, SMSigSK, SMSigScheme};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    // signatures are kept off the test's stack frame
+    fn signature(sk: &SMSigSK, index: usize, pp: &SMSigParam) -> Vec<u8> {
+        SMSigScheme::sign(sk, index, b"message", pp)
+            .unwrap()
+            .to_bytes()
+    }
+
+    fn wait_until_idle(sk: &SMSigSK) {
+        sk.cache.as_ref().unwrap().wait_until_idle()
+    }
+
+    #[test]
+    fn test_key_cache() {
+        with_large_stack(|| {
+            let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+            let pp = SMSigParam::setup_with_height(&mut rng, 4).unwrap();
+            let (_, sk) = SMSigScheme::key_gen(&[1u8; 32], &pp);
+            let cached = sk.clone().with_key_cache(2, 3, &pp);
+            assert!(cached.has_key_cache());
+            wait_until_idle(&cached);
+            assert_eq!(cached.cached_keys(), 3);
+
+            // the cached keys give the same signatures
+            assert_eq!(signature(&cached, 2, &pp), signature(&sk, 2, &pp));
+            wait_until_idle(&cached);
+            assert_eq!(cached.cached_keys(), 3);
+
+            // skipped indices are dropped, and missing ones generated
+            assert_eq!(signature(&cached, 6, &pp), signature(&sk, 6, &pp));
+            wait_until_idle(&cached);
+            assert_eq!(cached.cached_keys(), 1);
+            assert_eq!(signature(&cached, 7, &pp), signature(&sk, 7, &pp));
+            wait_until_idle(&cached);
+            assert_eq!(cached.cached_keys(), 0);
+
+            // earlier indices are not cached any more
+            assert_eq!(signature(&cached, 3, &pp), signature(&sk, 3, &pp));
+            assert!(!cached.clone().with_key_cache(0, 0, &pp).has_key_cache());
+
+            // the keys of other parameters are not used
+            let other_pp = SMSigParam::setup_with_height(&mut rng, 4).unwrap();
+            let (other_pk, other_sk) = SMSigScheme::key_gen(&[1u8; 32], &other_pp);
+            let cached = other_sk.with_key_cache(0, 2, &pp);
+            wait_until_idle(&cached);
+            let sig = SMSigScheme::sign(&cached, 0, b"message", &other_pp).unwrap();
+            assert!(SMSigScheme::verify(&other_pk, b"message", &sig, &other_pp));
+            assert_eq!(cached.cached_keys(), 2);
+        });
+    }
+}
@@ -0,0 +1,163 @@
+use super::{SMSigPK, SMSigParam, SMSigScheme, SMSignature};
+use crate::{
+    codec::{impl_serialization, Decode, Encode, Reader},
+    error::{Error, Result},
+    hots::HomomorphicOneTimeSignature,
+    poly::SmallPoly,
+    TreeTraversal, HOTS,
+};
+use std::fmt;
+use zeroize::Zeroize;
+
+/// A secret key that keeps O(HEIGHT^2) tree nodes instead of the whole tree.
+///
+/// One-time keys are used in increasing order of index: the paths are produced
+/// by a tree traversal, at the cost of a few one-time key generations per
+/// signature. Indices may be skipped, but never used again. The last index is
+/// reserved for the proof of possession, made with
+/// [`SMSigScheme::prove_possession`](super::SMSigScheme::prove_possession) from
+/// the [`SMSigSK`](super::SMSigSK) of the same seed. The seed is wiped on drop.
+#[derive(Clone, Default)]
+pub struct SMSigLazySK {
+    sk_seed: [u8; 32],
+    traversal: TreeTraversal,
+}
+
+impl Drop for SMSigLazySK {
+    fn drop(&mut self) {
+        self.sk_seed.zeroize();
+    }
+}
+
+impl fmt::Debug for SMSigLazySK {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SMSigLazySK")
+            .field("next_index", &self.next_index())
+            .finish_non_exhaustive()
+    }
+}
+
+/// The digest of the one-time public key at `index`, i.e., the leaf of the tree.
+fn leaf(seed: &[u8; 32], index: usize, pp: &SMSigParam) -> SmallPoly {
+    let (pk, _sk) = HOTS::key_gen(seed, index, &pp.hots_param);
+    pk.digest(&pp.hots_hasher)
+}
+
+impl SMSigLazySK {
+    /// Generate a key pair; this goes over every leaf once, but only keeps
+    /// O(HEIGHT) nodes at a time.
+    pub fn key_gen(seed: &[u8; 32], pp: &SMSigParam) -> (SMSigPK, Self) {
+        let (root, traversal) =
+            TreeTraversal::new(pp.height, |index| leaf(seed, index, pp), &pp.hvc_hasher)
+                .expect("the height of the parameters is valid");
+        (
+            root,
+            Self {
+                sk_seed: *seed,
+                traversal,
+            },
+        )
+    }
+
+    /// The smallest index that can still be used for signing.
+    pub fn next_index(&self) -> usize {
+        self.traversal.next_index()
+    }
+
+    /// Sign a message with the one-time key at `index`, skipping all unused
+    /// indices before it. Skipping costs about as much as signing. The index
+    /// of [`SMSigScheme::possession_index`] is rejected.
+    pub fn sign(&mut self, index: usize, message: &[u8], pp: &SMSigParam) -> Result<SMSignature> {
+        if self.traversal.height() != pp.height {
+            return Err(Error::InvalidHeight(self.traversal.height()));
+        }
+        if index >= self.traversal.num_leaves() {
+            return Err(Error::InvalidIndex(index));
+        }
+        if index == SMSigScheme::possession_index(pp) {
+            return Err(Error::ReservedIndex(index));
+        }
+        if index < self.next_index() {
+            return Err(Error::IndexReused(index));
+        }
+
+        let seed = &self.sk_seed;
+        let leaf_at = |i| leaf(seed, i, pp);
+        while self.next_index() < index {
+            let node = leaf_at(self.next_index());
+            self.traversal.next_path(&node, leaf_at, &pp.hvc_hasher)?;
+        }
+
+        let (hots_pk, hots_sk) = HOTS::key_gen(seed, index, &pp.hots_param);
+        let node = hots_pk.digest(&pp.hots_hasher);
+        let path = self.traversal.next_path(&node, leaf_at, &pp.hvc_hasher)?;
+        let hots_sig = HOTS::sign(&hots_sk, message);
+        Ok(SMSignature {
+            path: (&path).into(),
+            hots_pk: (&hots_pk).into(),
+            hots_sig,
+        })
+    }
+}
+
+impl Encode for SMSigLazySK {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.sk_seed);
+        self.traversal.encode(out);
+    }
+}
+
+impl Decode for SMSigLazySK {
+    fn decode_into(&mut self, reader: &mut Reader) -> Result<()> {
+        self.sk_seed = reader.read_array()?;
+        self.traversal.decode_into(reader)
+    }
+}
+
+impl_serialization!(SMSigLazySK);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{smsig::test::with_large_stack, MultiSig, SMSigScheme};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    // signatures are kept off the test's stack frame
+    fn sign_and_verify(sk: &mut SMSigLazySK, pk: &SMSigPK, index: usize, pp: &SMSigParam) {
+        let sig = sk.sign(index, b"message", pp).unwrap();
+        assert!(SMSigScheme::verify(pk, b"message", &sig, pp));
+    }
+
+    fn sign_error(sk: &mut SMSigLazySK, index: usize, pp: &SMSigParam) -> Error {
+        sk.sign(index, b"message", pp).unwrap_err()
+    }
+
+    #[test]
+    fn test_lazy_sk() {
+        with_large_stack(|| {
+            let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+            let pp = SMSigParam::setup_with_height(&mut rng, 4).unwrap();
+            let (pk, _) = SMSigScheme::key_gen(&[1u8; 32], &pp);
+            let (lazy_pk, mut sk) = SMSigLazySK::key_gen(&[1u8; 32], &pp);
+            assert_eq!(lazy_pk, pk);
+
+            sign_and_verify(&mut sk, &pk, 0, &pp);
+            sign_and_verify(&mut sk, &pk, 1, &pp);
+            // skip some indices
+            sign_and_verify(&mut sk, &pk, 5, &pp);
+            assert_eq!(sk.next_index(), 6);
+            assert_eq!(sign_error(&mut sk, 5, &pp), Error::IndexReused(5));
+            assert_eq!(sign_error(&mut sk, 3, &pp), Error::IndexReused(3));
+
+            // resume from the serialized state
+            let mut sk = SMSigLazySK::from_bytes(&sk.to_bytes()).unwrap();
+            assert_eq!(sign_error(&mut sk, 7, &pp), Error::ReservedIndex(7));
+            sign_and_verify(&mut sk, &pk, 6, &pp);
+            assert_eq!(sign_error(&mut sk, 8, &pp), Error::InvalidIndex(8));
+
+            let other_pp = SMSigParam::setup_with_height(&mut rng, 3).unwrap();
+            assert_eq!(sign_error(&mut sk, 6, &other_pp), Error::InvalidHeight(4));
+        });
+    }
+}
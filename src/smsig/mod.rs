@@ -1,37 +1,421 @@
+mod accountable;
+mod aggregator;
+mod batch;
+mod cache;
+mod lazy;
+mod possession;
+mod schedule;
+mod signer;
+mod subset;
+
+pub use aggregator::Aggregator;
+pub use batch::AggregateItem;
+pub use lazy::SMSigLazySK;
+pub use possession::ProofOfPossession;
+pub use schedule::{Clock, SlotSchedule, SystemClock};
+pub use signer::StatefulSigner;
+pub use subset::{SignerBitmap, SubsetSignature};
+
+use cache::KeyCache;
+
+use crate::codec::{
+    impl_serialization, BitReader, BitWriter, CoeffRange, Decode, Encode, Reader, ENCODING_VERSION,
+};
+use crate::error::{Error, Result};
 use crate::hots::{batch_verify_with_aggregated_pk, HomomorphicOneTimeSignature, RandomizedHOTSPK};
-use crate::poly::SmallPoly;
+use crate::param::check_height;
+use crate::poly::{HashMode, SmallPoly};
+use crate::progress::{no_progress, Counter, ProgressCallback, Stage, CHUNK_SIZE};
 use crate::randomizer::Randomizers;
 use crate::{
+    beta_agg, beta_sigma_agg,
     hots::{HotsParam, HotsSig},
-    HOTSHash, HVCHash, MultiSig, Path, HEIGHT, HOTS,
+    HOTSHash, HVCHash, MultiSig, Path, BETA_SIGMA, GAMMA, HEIGHT, HOTS, LARGE_MODULUS_BITS, N,
+    SMALL_MODULUS_BITS,
 };
-use crate::{RandomizedPath, Tree};
+use crate::{RandomizedPath, SignedPoly, Tree};
 use rand::Rng;
 #[cfg(feature = "parallel")]
-use rayon::iter::{IndexedParallelIterator, IntoParallelRefMutIterator, ParallelIterator};
+use rayon::{
+    iter::{IndexedParallelIterator, ParallelIterator},
+    slice::ParallelSliceMut,
+};
+use sha2::Digest;
+use std::{fmt, sync::Arc};
+use zeroize::Zeroize;
 
 pub struct SMSigScheme;
 
+/// Domain-separation tag of the randomizers of [`SMSigScheme`], which are bound to
+/// the message and the index; a new version of the aggregation gets a new tag.
+pub const SCHEME_ID: &[u8] = b"squirrel smsig v1";
+
 #[derive(Debug, Clone)]
 pub struct SMSigParam {
+    height: usize,
     hvc_hasher: HVCHash,
     hots_hasher: HOTSHash,
     hots_param: HotsParam,
 }
-#[derive(Debug, Clone)]
+
+impl Default for SMSigParam {
+    fn default() -> Self {
+        Self {
+            height: HEIGHT,
+            hvc_hasher: HVCHash::default(),
+            hots_hasher: HOTSHash::default(),
+            hots_param: HotsParam::default(),
+        }
+    }
+}
+
+impl SMSigParam {
+    /// Sample public parameters for trees of the given height, so that short-lived
+    /// keys do not need to build a tree of `1 << (HEIGHT - 1)` leaves.
+    pub fn setup_with_height<R: Rng>(rng: &mut R, height: usize) -> Result<Self> {
+        check_height(height)?;
+        let mut pp = SMSigScheme::setup(rng);
+        pp.height = height;
+        Ok(pp)
+    }
+
+    /// Derive the parameters from a public seed, such as [`DEFAULT_SETUP_SEED`]:
+    /// each entry of the hash matrices and of the HOTS parameter is expanded from
+    /// the seed with its own domain-separated SHAKE256 call. Anyone can thus
+    /// regenerate the parameters and compare their [`fingerprint`](Self::fingerprint).
+    ///
+    /// [`DEFAULT_SETUP_SEED`]: crate::DEFAULT_SETUP_SEED
+    pub fn setup_from_seed(seed: &[u8], height: usize) -> Result<Self> {
+        check_height(height)?;
+        Ok(Self {
+            height,
+            hvc_hasher: HVCHash::from_seed(seed),
+            hots_hasher: HOTSHash::from_seed(seed),
+            hots_param: HotsParam::from_seed(seed),
+        })
+    }
+
+    /// A SHA-256 digest of the encoded parameters, including the height.
+    pub fn fingerprint(&self) -> [u8; 32] {
+        sha2::Sha256::digest(self.to_bytes()).into()
+    }
+
+    /// The height of the trees; each key has `1 << (height - 1)` one-time keys.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Select how both hashes are evaluated. [`HashMode::FixedBase`] speeds up
+    /// key generation and verification; the keys, signatures and fingerprint
+    /// are the same in both modes, and decoded parameters use the default.
+    pub fn with_hash_mode(mut self, mode: HashMode) -> Self {
+        self.hvc_hasher = self.hvc_hasher.with_mode(mode);
+        self.hots_hasher = self.hots_hasher.with_mode(mode);
+        self
+    }
+}
+/// A secret key: the seed of the one-time keys, wiped on drop, and the tree
+/// of their public keys. It may also hold a cache of the one-time keys of the
+/// next indices, see [`SMSigSK::with_key_cache`]; clones share the cache.
+#[derive(Clone, Default)]
 pub struct SMSigSK {
     sk_seed: [u8; 32],
     tree: Tree,
+    cache: Option<Arc<KeyCache>>,
+}
+
+impl Drop for SMSigSK {
+    fn drop(&mut self) {
+        self.sk_seed.zeroize();
+    }
+}
+
+impl fmt::Debug for SMSigSK {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SMSigSK")
+            .field("height", &self.tree.height())
+            .field("cached_keys", &self.cached_keys())
+            .finish_non_exhaustive()
+    }
+}
+
+impl SMSigSK {
+    /// Write the tree of the key to a file, so that the key can be restored with
+    /// [`SMSigSK::open_tree`] instead of being generated again.
+    /// The file only contains public data; the seed is not stored.
+    pub fn save_tree(&self, path: impl AsRef<std::path::Path>, pp: &SMSigParam) -> Result<()> {
+        self.tree.save(path, &pp.hvc_hasher)
+    }
+
+    /// Restore a key from its seed and a tree file written by [`SMSigSK::save_tree`].
+    /// The tree is memory-mapped, so nodes are only read when signing.
+    ///
+    /// # Safety
+    ///
+    /// As for [`Tree::open`], the file must not be truncated or rewritten while
+    /// the key or a clone of it is alive.
+    pub fn open_tree(
+        seed: &[u8; 32],
+        path: impl AsRef<std::path::Path>,
+        pp: &SMSigParam,
+    ) -> Result<Self> {
+        let tree = Tree::open(path, &pp.hvc_hasher)?;
+        if tree.height() != pp.height {
+            return Err(Error::InvalidHeight(tree.height()));
+        }
+        // check that the tree belongs to the seed with its first leaf
+        let (pk, _sk) = HOTS::key_gen(seed, 0, &pp.hots_param);
+        if tree.leaf(0)? != pk.digest(&pp.hots_hasher) {
+            return Err(Error::KeyMismatch);
+        }
+        Ok(Self {
+            sk_seed: *seed,
+            tree,
+            cache: None,
+        })
+    }
+
+    /// Generate the one-time keys of the next `capacity` indices, from
+    /// `next_index` on, in a background thread, so that signing at these
+    /// indices only computes the signature and the path. Each index that is
+    /// signed drops the cached keys of the indices before it, and the thread
+    /// refills the cache after it; a signature at an index that is not in the
+    /// cache, or with other parameters than `pp`, generates its keys as usual.
+    /// A capacity of zero removes the cache, and so does a failure to spawn
+    /// the thread, see [`SMSigSK::has_key_cache`].
+    pub fn with_key_cache(mut self, next_index: usize, capacity: usize, pp: &SMSigParam) -> Self {
+        self.cache = (capacity > 0)
+            .then(|| {
+                let num_indices = self.tree.num_leaves();
+                KeyCache::new(&self.sk_seed, pp, next_index, num_indices, capacity).ok()
+            })
+            .flatten()
+            .map(Arc::new);
+        self
+    }
+
+    /// Whether the key has a cache of one-time keys.
+    pub fn has_key_cache(&self) -> bool {
+        self.cache.is_some()
+    }
+
+    /// The number of one-time keys in the cache.
+    pub fn cached_keys(&self) -> usize {
+        self.cache.as_ref().map_or(0, |cache| cache.len())
+    }
 }
 
 pub type SMSigPK = SmallPoly;
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct SMSignature {
     path: RandomizedPath,
     hots_pk: RandomizedHOTSPK,
     hots_sig: HotsSig,
 }
 
+impl Encode for SMSigParam {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.push(self.height as u8);
+        self.hvc_hasher.encode(out);
+        self.hots_hasher.encode(out);
+        self.hots_param.encode(out);
+    }
+}
+
+impl Decode for SMSigParam {
+    fn decode_into(&mut self, reader: &mut Reader) -> Result<()> {
+        self.height = reader.read_u8()? as usize;
+        check_height(self.height)?;
+        self.hvc_hasher.decode_into(reader)?;
+        self.hots_hasher.decode_into(reader)?;
+        self.hots_param.decode_into(reader)
+    }
+}
+
+impl_serialization!(SMSigParam);
+
+impl Encode for SMSigSK {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.sk_seed);
+        self.tree.encode(out);
+    }
+}
+
+impl Decode for SMSigSK {
+    fn decode_into(&mut self, reader: &mut Reader) -> Result<()> {
+        self.sk_seed = reader.read_array()?;
+        self.tree.decode_into(reader)
+    }
+}
+
+impl_serialization!(SMSigSK);
+
+impl Encode for SMSignature {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.path.encode(out);
+        self.hots_pk.encode(out);
+        self.hots_sig.encode(out);
+    }
+}
+
+impl Decode for SMSignature {
+    fn decode_into(&mut self, reader: &mut Reader) -> Result<()> {
+        self.path.decode_into(reader)?;
+        self.hots_pk.decode_into(reader)?;
+        self.hots_sig.decode_into(reader)?;
+        if self.path.is_randomized != self.hots_pk.is_randomized
+            || self.path.is_randomized != self.hots_sig.is_randomized
+        {
+            return Err(Error::InconsistentRandomization);
+        }
+        Ok(())
+    }
+}
+
+impl_serialization!(SMSignature);
+
+/// Coefficient ranges of the decompositions and of sigma in the compact encoding
+/// of a signature for trees of `height`.
+fn compact_ranges(
+    is_randomized: bool,
+    num_signers: usize,
+    height: usize,
+) -> (CoeffRange, CoeffRange) {
+    if is_randomized {
+        (
+            CoeffRange::signed(beta_agg(num_signers, height)),
+            CoeffRange::signed(beta_sigma_agg(num_signers)),
+        )
+    } else {
+        (CoeffRange::BINARY, CoeffRange::signed(BETA_SIGMA))
+    }
+}
+
+impl SMSignature {
+    /// The index of the one-time key that signed, shared by all signers of an aggregate.
+    pub fn index(&self) -> usize {
+        self.path.index
+    }
+
+    /// Whether the signature is an aggregate.
+    pub fn is_aggregated(&self) -> bool {
+        self.path.is_randomized
+    }
+
+    /// randomize a fresh signature, which can then be added to other randomized ones
+    fn randomize_with(&mut self, randomizer: &SignedPoly) -> Result<()> {
+        self.hots_pk.randomize_with(randomizer)?;
+        self.hots_sig.randomize_with(randomizer)?;
+        self.path.randomize_with(randomizer)
+    }
+
+    /// add a randomized signature for the same index and height
+    fn add_randomized(&mut self, other: &Self) {
+        self.hots_pk += other.hots_pk;
+        self.hots_sig += other.hots_sig;
+        self.path = &self.path + &other.path;
+    }
+
+    /// add another aggregate, after checking that both are for the same
+    /// index and height
+    fn merge(&mut self, other: &Self) -> Result<()> {
+        if !self.is_aggregated() || !other.is_aggregated() {
+            return Err(Error::NotRandomized);
+        }
+        if self.path.index != other.path.index {
+            return Err(Error::MixedIndices(self.path.index, other.path.index));
+        }
+        if self.path.nodes.len() != other.path.nodes.len() {
+            return Err(Error::InvalidPathLength(other.path.nodes.len()));
+        }
+        self.add_randomized(other);
+        Ok(())
+    }
+
+    /// Size in bytes of the compact encoding of a signature for trees of `height`.
+    /// `num_signers` is only used for aggregated signatures.
+    pub fn compact_size(height: usize, is_randomized: bool, num_signers: usize) -> usize {
+        let (decomposed, sigma) = compact_ranges(is_randomized, num_signers, height);
+        let num_decomposed = (2 * (height - 1) * SMALL_MODULUS_BITS + 2 * LARGE_MODULUS_BITS) * N;
+        let bits = (height - 1)
+            + num_decomposed * decomposed.width() as usize
+            + GAMMA * N * sigma.width() as usize;
+        // version, flag and height bytes
+        3 + bits.div_ceil(8)
+    }
+
+    /// Serialize into the compact encoding, where each coefficient is packed with
+    /// the minimal number of bits allowed by the norm bounds.
+    /// `num_signers` is the number of signers of an aggregated signature and is
+    /// ignored for fresh signatures.
+    /// Returns an error if a coefficient exceeds the norm bound.
+    pub fn to_compact_bytes(&self, num_signers: usize) -> Result<Vec<u8>> {
+        let is_randomized = self.path.is_randomized;
+        if is_randomized != self.hots_pk.is_randomized
+            || is_randomized != self.hots_sig.is_randomized
+        {
+            return Err(Error::InconsistentRandomization);
+        }
+        let path_len = self.path.nodes.len();
+        let (decomposed, sigma) = compact_ranges(is_randomized, num_signers, path_len + 1);
+
+        let mut writer = BitWriter::new(vec![
+            ENCODING_VERSION,
+            is_randomized as u8,
+            path_len as u8 + 1,
+        ]);
+        writer.write(self.path.index as u32, path_len as u32);
+        for (left, right) in self.path.nodes.iter() {
+            for poly in left.iter().chain(right.iter()) {
+                writer.write_coeffs(&poly.coeffs, decomposed)?;
+            }
+        }
+        for poly in self.hots_pk.v0.iter().chain(self.hots_pk.v1.iter()) {
+            writer.write_coeffs(&poly.coeffs, decomposed)?;
+        }
+        for poly in self.hots_sig.sigma.iter() {
+            writer.write_large_poly(poly, sigma)?;
+        }
+        Ok(writer.finish())
+    }
+
+    /// Deserialize from the compact encoding.
+    /// `num_signers` must match the one used for serialization.
+    pub fn from_compact_bytes(bytes: &[u8], num_signers: usize) -> Result<Self> {
+        let mut reader = Reader::new(bytes);
+        let version = reader.read_u8()?;
+        if version != ENCODING_VERSION {
+            return Err(Error::UnsupportedVersion(version));
+        }
+        let is_randomized = reader.read_bool()?;
+        let height = reader.read_u8()? as usize;
+        check_height(height)?;
+        let (decomposed, sigma) = compact_ranges(is_randomized, num_signers, height);
+
+        let mut res = Self::default();
+        res.path.nodes.resize(height - 1, Default::default());
+        let mut reader = BitReader::new(reader.read_bytes(reader.remaining())?);
+        res.path.index = reader.read(height as u32 - 1)? as usize;
+        for (left, right) in res.path.nodes.iter_mut() {
+            for poly in left.iter_mut().chain(right.iter_mut()) {
+                reader.read_coeffs(&mut poly.coeffs, decomposed)?;
+            }
+        }
+        for poly in res.hots_pk.v0.iter_mut().chain(res.hots_pk.v1.iter_mut()) {
+            reader.read_coeffs(&mut poly.coeffs, decomposed)?;
+        }
+        for poly in res.hots_sig.sigma.iter_mut() {
+            reader.read_large_poly(poly, sigma)?;
+        }
+        reader.finish()?;
+
+        res.path.is_randomized = is_randomized;
+        res.hots_pk.is_randomized = is_randomized;
+        res.hots_sig.is_randomized = is_randomized;
+        Ok(res)
+    }
+}
+
 impl MultiSig for SMSigScheme {
     type Param = SMSigParam;
     type PK = SMSigPK;
@@ -40,6 +424,7 @@ impl MultiSig for SMSigScheme {
 
     fn setup<R: Rng>(rng: &mut R) -> Self::Param {
         Self::Param {
+            height: HEIGHT,
             hvc_hasher: HVCHash::init(rng),
             hots_hasher: HOTSHash::init(rng),
             hots_param: HOTS::setup(rng),
@@ -47,60 +432,29 @@ impl MultiSig for SMSigScheme {
     }
 
     fn key_gen(seed: &[u8; 32], pp: &Self::Param) -> (Self::PK, Self::SK) {
-        // let mut pk_digests = Vec::new();
-        // for index in 0..1 << HEIGHT - 1 {
-        //     let (pk, _sk) = HOTS::key_gen(seed, index, &pp.hots_param);
-        //     pk_digests.push(pk.digest(&pp.hots_hasher));
-        // }
-        // let tree = Tree::new_with_leaf_nodes(&pk_digests, &pp.hvc_hasher);
-
-        // (
-        //     tree.root(),
-        //     SMSigSK {
-        //         sk_seed: *seed,
-        //         tree,
-        //     },
-        // )
-
-        let mut pk_digests = vec![SmallPoly::default(); 1 << (HEIGHT - 1)];
-
-        #[cfg(not(feature = "parallel"))]
-        pk_digests.iter_mut().enumerate().for_each(|(index, pkd)| {
-            let (pk, _sk) = HOTS::key_gen(seed, index, &pp.hots_param);
-            *pkd = pk.digest(&pp.hots_hasher)
-        });
-
-        #[cfg(feature = "parallel")]
-        pk_digests
-            .par_iter_mut()
-            .enumerate()
-            .for_each(|(index, pkd)| {
-                let (pk, _sk) = HOTS::key_gen(seed, index, &pp.hots_param);
-                *pkd = pk.digest(&pp.hots_hasher)
-            });
-
-        let tree = Tree::new_with_leaf_nodes(&pk_digests, &pp.hvc_hasher);
-
-        (
-            tree.root(),
-            SMSigSK {
-                sk_seed: *seed,
-                tree,
-            },
-        )
+        Self::key_gen_with_progress(seed, pp, &no_progress)
     }
 
-    fn sign(sk: &Self::SK, index: usize, message: &[u8], pp: &Self::Param) -> Self::Signature {
-        let path = sk.tree.gen_proof(index);
-        let (hots_pk, hots_sk) = HOTS::key_gen(&sk.sk_seed, index, &pp.hots_param);
+    fn sign(
+        sk: &Self::SK,
+        index: usize,
+        message: &[u8],
+        pp: &Self::Param,
+    ) -> Result<Self::Signature> {
+        if sk.tree.height() != pp.height {
+            return Err(Error::InvalidHeight(sk.tree.height()));
+        }
+        let path = sk.tree.gen_proof(index)?;
+        let (hots_pk, hots_sk) = match sk.cache.as_ref().and_then(|cache| cache.take(index, pp)) {
+            Some(keys) => keys,
+            None => HOTS::key_gen(&sk.sk_seed, index, &pp.hots_param),
+        };
         let hots_sig = HOTS::sign(&hots_sk, message);
-        SMSignature {
+        Ok(SMSignature {
             path: (&path).into(),
             hots_pk: (&hots_pk).into(),
             hots_sig,
-        }
-        // // SMSignature::default()
-        // todo!()
+        })
     }
 
     fn verify(pk: &Self::PK, message: &[u8], sig: &Self::Signature, pp: &Self::Param) -> bool {
@@ -112,68 +466,627 @@ impl MultiSig for SMSigScheme {
         }
 
         // check hots public key membership
+        if sig.path.nodes.len() != pp.height - 1 {
+            return false;
+        }
         let path = Path::from(&sig.path);
         if !path.verify(pk, &pp.hvc_hasher) {
             return false;
         }
         let pk_digest = hots_pk.digest(&pp.hots_hasher);
         if sig.path.index & 1 == 0 {
-            pk_digest == path.nodes[HEIGHT - 2].0
+            pk_digest == path.nodes[pp.height - 2].0
         } else {
-            pk_digest == path.nodes[HEIGHT - 2].1
+            pk_digest == path.nodes[pp.height - 2].1
         }
     }
 
-    fn aggregate(sigs: &[Self::Signature], roots: &[SmallPoly]) -> Self::Signature {
-        let randomizers = Randomizers::from_pks(roots);
+    fn aggregate(
+        sigs: &[Self::Signature],
+        roots: &[SmallPoly],
+        message: &[u8],
+    ) -> Result<Self::Signature> {
+        if sigs.len() != roots.len() {
+            return Err(Error::LengthMismatch(roots.len(), sigs.len()));
+        }
+        let index = sigs.first().map(|x| x.path.index).unwrap_or_default();
+        let randomizers = Randomizers::from_pks_and_message(SCHEME_ID, roots, message, index);
+        Self::aggregate_with_randomizers(sigs, &randomizers)
+    }
 
+    fn batch_verify(
+        pks: &[Self::PK],
+        message: &[u8],
+        sig: &Self::Signature,
+        pp: &Self::Param,
+    ) -> bool {
+        let randomizers =
+            Randomizers::from_pks_and_message(SCHEME_ID, pks, message, sig.path.index);
+        Self::batch_verify_with_randomizers(pks, message, sig, &randomizers, pp)
+    }
+}
+
+impl SMSigScheme {
+    /// Generate a key pair as [`MultiSig::key_gen`], and report the computed
+    /// leaves and nodes of the tree to `progress`.
+    pub fn key_gen_with_progress(
+        seed: &[u8; 32],
+        pp: &SMSigParam,
+        progress: ProgressCallback,
+    ) -> (SMSigPK, SMSigSK) {
+        let mut pk_digests = vec![SmallPoly::default(); 1 << (pp.height - 1)];
+        let counter = Counter::new(Stage::Leaves, pk_digests.len(), progress);
+        let digest_chunk = |(chunk_index, chunk): (usize, &mut [SmallPoly])| {
+            for (i, pkd) in chunk.iter_mut().enumerate() {
+                let (pk, _sk) = HOTS::key_gen(seed, chunk_index * CHUNK_SIZE + i, &pp.hots_param);
+                *pkd = pk.digest(&pp.hots_hasher)
+            }
+            counter.add(chunk.len());
+        };
+
+        #[cfg(not(feature = "parallel"))]
+        pk_digests
+            .chunks_mut(CHUNK_SIZE)
+            .enumerate()
+            .for_each(digest_chunk);
+
+        #[cfg(feature = "parallel")]
+        pk_digests
+            .par_chunks_mut(CHUNK_SIZE)
+            .enumerate()
+            .for_each(digest_chunk);
+
+        let tree = Tree::build_with_progress(&pk_digests, &pp.hvc_hasher, progress);
+
+        (
+            tree.root(),
+            SMSigSK {
+                sk_seed: *seed,
+                tree,
+                cache: None,
+            },
+        )
+    }
+
+    /// Aggregate signatures with the given randomizers. [`MultiSig::aggregate`]
+    /// uses the ones of [`SCHEME_ID`]; passing [`Randomizers::from_pks`] gives
+    /// the aggregates of the original scheme.
+    pub fn aggregate_with_randomizers(
+        sigs: &[SMSignature],
+        randomizers: &Randomizers,
+    ) -> Result<SMSignature> {
         // aggregate HOTS pk
         let pks: Vec<RandomizedHOTSPK> = sigs.iter().map(|x| x.hots_pk).collect();
-        let agg_pk = RandomizedHOTSPK::aggregate_with_randomizers(&pks, &randomizers);
+        let agg_pk = RandomizedHOTSPK::aggregate_with_randomizers(&pks, randomizers)?;
 
         // aggregate HOTS sig
         let hots_sigs: Vec<HotsSig> = sigs.iter().map(|x| x.hots_sig).collect();
-        let agg_sig = HotsSig::aggregate_with_randomizers(&hots_sigs, &randomizers);
+        let agg_sig = HotsSig::aggregate_with_randomizers(&hots_sigs, randomizers)?;
 
         // aggregate the membership proof
         let membership_proofs: Vec<RandomizedPath> = sigs.iter().map(|x| x.path.clone()).collect();
         let agg_proof =
-            RandomizedPath::aggregate_with_randomizers(&membership_proofs, &randomizers);
-        Self::Signature {
+            RandomizedPath::aggregate_with_randomizers(&membership_proofs, randomizers)?;
+        Ok(SMSignature {
             path: agg_proof,
             hots_pk: agg_pk,
             hots_sig: agg_sig,
-        }
+        })
     }
 
-    fn batch_verify(
-        pks: &[Self::PK],
+    /// Verify an aggregate made by [`SMSigScheme::aggregate_with_randomizers`]
+    /// with the same randomizers.
+    pub fn batch_verify_with_randomizers(
+        pks: &[SMSigPK],
         message: &[u8],
-        sig: &Self::Signature,
-        pp: &Self::Param,
+        sig: &SMSignature,
+        randomizers: &Randomizers,
+        pp: &SMSigParam,
     ) -> bool {
-        if !batch_verify_with_aggregated_pk(&sig.hots_pk, message, &sig.hots_sig, &pp.hots_param) {
+        // the bounds of an empty committee only admit a zero signature, which
+        // would verify for any message
+        if pks.is_empty() || sig.path.nodes.len() != pp.height - 1 {
             return false;
         }
-        if !sig.path.verify(pks, &pp.hvc_hasher) {
+
+        // the randomized public keys and paths must be short,
+        // otherwise they can be shifted by multiples of the modulus
+        let norm_bound = beta_agg(pks.len(), pp.height);
+        if sig.hots_pk.infinity_norm() > norm_bound || sig.path.infinity_norm() > norm_bound {
+            return false;
+        }
+
+        if !batch_verify_with_aggregated_pk(
+            &sig.hots_pk,
+            message,
+            &sig.hots_sig,
+            pks.len(),
+            &pp.hots_param,
+        ) {
+            return false;
+        }
+        if !sig
+            .path
+            .verify_with_randomizers(pks, randomizers, &pp.hvc_hasher)
+        {
             return false;
         }
         if sig.path.index & 1 == 0 {
             sig.hots_pk.digest(&pp.hots_hasher)
-                == SmallPoly::projection(&sig.path.nodes[HEIGHT - 2].0)
+                == SmallPoly::projection(&sig.path.nodes[pp.height - 2].0)
         } else {
             sig.hots_pk.digest(&pp.hots_hasher)
-                == SmallPoly::projection(&sig.path.nodes[HEIGHT - 2].1)
+                == SmallPoly::projection(&sig.path.nodes[pp.height - 2].1)
         }
     }
 }
 
 #[cfg(test)]
-mod test {
+pub(crate) mod test {
     use super::*;
+    use crate::{Progress, DEFAULT_SETUP_SEED, ENCODING_VERSION, MAX_HEIGHT, SMALL_MODULUS};
     use rand::{RngCore, SeedableRng};
     use rand_chacha::ChaCha20Rng;
 
+    // signatures that have the shape of real ones but do not verify
+    fn random_signatures<R: Rng>(
+        rng: &mut R,
+        pp: &SMSigParam,
+        num_signatures: usize,
+    ) -> (Vec<SMSignature>, Vec<SMSigPK>) {
+        let mut sigs = vec![SMSignature::default(); num_signatures];
+        let mut roots = vec![SMSigPK::default(); num_signatures];
+        let mut seed = [0u8; 32];
+        for (sig, root) in sigs.iter_mut().zip(roots.iter_mut()) {
+            rng.fill_bytes(&mut seed);
+            let (path, path_root) = Path::random_for_testing(rng, &pp.hvc_hasher);
+            let (hots_pk, hots_sk) = HOTS::key_gen(&seed, 0, &pp.hots_param);
+            sig.path = (&path).into();
+            sig.hots_pk = (&hots_pk).into();
+            sig.hots_sig = HOTS::sign(&hots_sk, "message".as_ref());
+            *root = path_root;
+        }
+        (sigs, roots)
+    }
+
+    // checks are done in separate functions to keep
+    // the large objects off the test's stack frame
+    fn assert_round_trip(sig: &SMSignature) {
+        let bytes = sig.to_bytes();
+        assert_eq!(SMSignature::from_bytes(&bytes).unwrap().to_bytes(), bytes);
+
+        with_large_stack(|| {
+            let bin = bincode::serialize(sig).unwrap();
+            let rec: SMSignature = bincode::deserialize(&bin).unwrap();
+            assert_eq!(rec.to_bytes(), bytes);
+
+            let json = serde_json::to_string(sig).unwrap();
+            let rec: SMSignature = serde_json::from_str(&json).unwrap();
+            assert_eq!(rec.to_bytes(), bytes);
+        });
+    }
+
+    // serde and the signing path keep several copies of the large signature types
+    // on the stack in debug builds, which is more than the 2MB that test threads
+    // get by default
+    pub(crate) fn with_large_stack<F: FnOnce() + Send>(f: F) {
+        std::thread::scope(|s| {
+            std::thread::Builder::new()
+                .stack_size(16 << 20)
+                .spawn_scoped(s, f)
+                .unwrap()
+                .join()
+                .unwrap()
+        });
+    }
+
+    fn assert_param_round_trip(pp: &SMSigParam) {
+        let bytes = pp.to_bytes();
+        assert_eq!(SMSigParam::from_bytes(&bytes).unwrap().to_bytes(), bytes);
+    }
+
+    fn assert_compact_round_trip(sig: &SMSignature, num_signers: usize) {
+        let compact = sig.to_compact_bytes(num_signers).unwrap();
+        assert_eq!(
+            compact.len(),
+            SMSignature::compact_size(
+                sig.path.nodes.len() + 1,
+                sig.path.is_randomized,
+                num_signers
+            )
+        );
+        assert!(compact.len() < sig.to_bytes().len());
+        let rec = SMSignature::from_compact_bytes(&compact, num_signers).unwrap();
+        assert_eq!(rec.to_bytes(), sig.to_bytes());
+    }
+
+    fn assert_compact_aggregate(sigs: &[SMSignature], roots: &[SMSigPK]) {
+        let agg_sig = SMSigScheme::aggregate(sigs, roots, b"message").unwrap();
+        assert_compact_round_trip(&agg_sig, sigs.len());
+        assert_compact_decoding_failures(&agg_sig, sigs.len());
+    }
+
+    fn assert_compact_decoding_failures(sig: &SMSignature, num_signers: usize) {
+        let compact = sig.to_compact_bytes(num_signers).unwrap();
+        let decoding_error =
+            |bytes: &[u8]| SMSignature::from_compact_bytes(bytes, num_signers).unwrap_err();
+
+        assert_eq!(
+            decoding_error(&compact[..compact.len() - 1]),
+            Error::UnexpectedEnd
+        );
+        let mut invalid = compact.clone();
+        invalid.push(0);
+        assert_eq!(decoding_error(&invalid), Error::TrailingBytes(1));
+        let mut invalid = compact;
+        *invalid.last_mut().unwrap() |= 0x80;
+        assert_eq!(decoding_error(&invalid), Error::InvalidPadding);
+    }
+
+    fn decoding_error(bytes: &[u8]) -> Error {
+        SMSignature::from_bytes(bytes).unwrap_err()
+    }
+
+    #[test]
+    fn test_serialization() {
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+        let pp = SMSigScheme::setup(&mut rng);
+        assert_param_round_trip(&pp);
+
+        // fresh signatures
+        let (sigs, roots) = random_signatures(&mut rng, &pp, 10);
+        sigs.iter().for_each(assert_round_trip);
+        for root in roots.iter() {
+            assert_eq!(SMSigPK::from_bytes(&root.to_bytes()).unwrap(), *root);
+        }
+
+        // aggregated signatures
+        assert_round_trip(&SMSigScheme::aggregate(&sigs, &roots, b"message").unwrap());
+    }
+
+    #[test]
+    fn test_compact_encoding() {
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+        let pp = SMSigScheme::setup(&mut rng);
+        let (mut sigs, roots) = random_signatures(&mut rng, &pp, 10);
+        sigs.iter()
+            .for_each(|sig| assert_compact_round_trip(sig, 1));
+        assert_compact_aggregate(&sigs, &roots);
+
+        // one bit per fresh decomposed coefficient
+        assert!(
+            SMSignature::compact_size(HEIGHT, false, 1)
+                < SMSignature::compact_size(HEIGHT, true, 1)
+        );
+        assert!(
+            SMSignature::compact_size(HEIGHT, true, 1024)
+                < SMSignature::compact_size(HEIGHT, true, 4096)
+        );
+        assert!(
+            SMSignature::compact_size(5, true, 1024)
+                < SMSignature::compact_size(HEIGHT, true, 1024)
+        );
+
+        // coefficients above the declared bound cannot be encoded
+        sigs[0].path.nodes[0].0[0].coeffs[0] = 2;
+        assert_eq!(
+            sigs[0].to_compact_bytes(1).unwrap_err(),
+            Error::InvalidCoefficient(2)
+        );
+    }
+
+    fn aggregation_error(sigs: &[SMSignature], roots: &[SMSigPK]) -> Error {
+        SMSigScheme::aggregate(sigs, roots, b"message").unwrap_err()
+    }
+
+    fn reaggregation_error(sigs: &[SMSignature], roots: &[SMSigPK]) -> Error {
+        let agg_sig = SMSigScheme::aggregate(sigs, roots, b"message").unwrap();
+        aggregation_error(std::slice::from_ref(&agg_sig), &roots[..1])
+    }
+
+    #[test]
+    fn test_aggregation_errors() {
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+        let pp = SMSigScheme::setup(&mut rng);
+        let (mut sigs, roots) = random_signatures(&mut rng, &pp, 3);
+
+        assert_eq!(
+            aggregation_error(&sigs[..2], &roots),
+            Error::LengthMismatch(3, 2)
+        );
+        assert_eq!(aggregation_error(&[], &[]), Error::EmptyInput);
+        assert_eq!(reaggregation_error(&sigs, &roots), Error::AlreadyRandomized);
+
+        sigs[2].path.index = 1;
+        assert_eq!(aggregation_error(&sigs, &roots), Error::MixedIndices(0, 1));
+    }
+
+    #[test]
+    fn test_empty_committee() {
+        with_large_stack(|| {
+            let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+            let pp = SMSigParam::setup_with_height(&mut rng, 3).unwrap();
+            let mut zero_sig = SMSignature::default();
+            zero_sig.path.nodes.resize(2, Default::default());
+            zero_sig.path.is_randomized = true;
+            zero_sig.hots_pk.is_randomized = true;
+            zero_sig.hots_sig.is_randomized = true;
+            assert!(!SMSigScheme::batch_verify(&[], b"message", &zero_sig, &pp));
+            assert!(!batch_verify_with_aggregated_pk(
+                &zero_sig.hots_pk,
+                b"message",
+                &zero_sig.hots_sig,
+                0,
+                &pp.hots_param
+            ));
+        });
+    }
+
+    fn setup_error<R: Rng>(rng: &mut R, height: usize) -> Error {
+        SMSigParam::setup_with_height(rng, height).unwrap_err()
+    }
+
+    fn mismatched_height_error<R: Rng>(rng: &mut R, sk: &SMSigSK) -> Error {
+        let pp = SMSigParam::setup_with_height(rng, sk.tree.height() + 1).unwrap();
+        signing_error(sk, 0, &pp)
+    }
+
+    fn signing_error(sk: &SMSigSK, index: usize, pp: &SMSigParam) -> Error {
+        SMSigScheme::sign(sk, index, b"message", pp).unwrap_err()
+    }
+
+    fn sign_and_aggregate(
+        sks: &[SMSigSK],
+        pks: &[SMSigPK],
+        index: usize,
+        message: &[u8],
+        pp: &SMSigParam,
+    ) {
+        let sigs: Vec<SMSignature> = sks
+            .iter()
+            .map(|sk| SMSigScheme::sign(sk, index, message, pp).unwrap())
+            .collect();
+        for (pk, sig) in pks.iter().zip(sigs.iter()) {
+            assert!(SMSigScheme::verify(pk, message, sig, pp));
+            assert_round_trip(sig);
+        }
+
+        assert_batch_verify(&sigs, pks, message, pp);
+        assert_compact_aggregate(&sigs, pks);
+    }
+
+    fn assert_batch_verify(sigs: &[SMSignature], pks: &[SMSigPK], message: &[u8], pp: &SMSigParam) {
+        let agg_sig = SMSigScheme::aggregate(sigs, pks, message).unwrap();
+        assert!(SMSigScheme::batch_verify(pks, message, &agg_sig, pp));
+        assert!(!SMSigScheme::batch_verify(
+            pks,
+            b"another message",
+            &agg_sig,
+            pp
+        ));
+        // the randomizers are bound to the message
+        let other_agg_sig = SMSigScheme::aggregate(sigs, pks, b"another message").unwrap();
+        assert!(!SMSigScheme::batch_verify(pks, message, &other_agg_sig, pp));
+
+        // aggregates with the randomizers of the original scheme
+        let legacy = Randomizers::from_pks(pks);
+        let legacy_agg_sig = SMSigScheme::aggregate_with_randomizers(sigs, &legacy).unwrap();
+        assert!(SMSigScheme::batch_verify_with_randomizers(
+            pks,
+            message,
+            &legacy_agg_sig,
+            &legacy,
+            pp
+        ));
+        assert!(!SMSigScheme::batch_verify(
+            pks,
+            message,
+            &legacy_agg_sig,
+            pp
+        ));
+        assert!(!SMSigScheme::batch_verify_with_randomizers(
+            pks, message, &agg_sig, &legacy, pp
+        ));
+
+        // shifting a coefficient by the modulus does not change the projection,
+        // but must be rejected by the norm check
+        let mut forged_sig = agg_sig;
+        forged_sig.path.nodes[0].0[0].coeffs[0] += SMALL_MODULUS as i32;
+        assert!(!SMSigScheme::batch_verify(pks, message, &forged_sig, pp));
+    }
+
+    #[test]
+    fn test_small_height() {
+        with_large_stack(|| {
+            let message = "this is the message to sign";
+            let mut seed = [0u8; 32];
+            let mut rng = ChaCha20Rng::from_seed(seed);
+            let height = 4;
+
+            assert_eq!(setup_error(&mut rng, 1), Error::InvalidHeight(1));
+            assert_eq!(
+                setup_error(&mut rng, MAX_HEIGHT + 1),
+                Error::InvalidHeight(MAX_HEIGHT + 1)
+            );
+            let pp = SMSigParam::setup_with_height(&mut rng, height).unwrap();
+            assert_eq!(pp.height(), height);
+            // the norm bound of aggregates is tighter for lower trees
+            assert!(beta_agg(1000, height) < beta_agg(1000, HEIGHT));
+            assert_param_round_trip(&pp);
+
+            let mut pks = Vec::new();
+            let mut sks = Vec::new();
+            for _ in 0..4 {
+                rng.fill_bytes(&mut seed);
+                let (pk, sk) = SMSigScheme::key_gen(&seed, &pp);
+                assert_eq!(sk.tree.height(), height);
+                pks.push(pk);
+                sks.push(sk);
+            }
+            for index in [0, 5, (1 << (height - 1)) - 1] {
+                sign_and_aggregate(&sks, &pks, index, message.as_ref(), &pp);
+            }
+
+            // secret keys round trip
+            let sk_rec = SMSigSK::from_bytes(&sks[0].to_bytes()).unwrap();
+            assert_eq!(sk_rec.to_bytes(), sks[0].to_bytes());
+
+            // out of range indices and mismatched heights
+            assert_eq!(
+                signing_error(&sks[0], 1 << (height - 1), &pp),
+                Error::InvalidIndex(1 << (height - 1))
+            );
+            assert_eq!(
+                mismatched_height_error(&mut rng, &sks[0]),
+                Error::InvalidHeight(height)
+            );
+
+            // secret keys restored from a tree file
+            let dir = tempfile::tempdir().unwrap();
+            let path = dir.path().join("tree");
+            sks[0].save_tree(&path, &pp).unwrap();
+            let sk_mapped = SMSigSK::open_tree(&sks[0].sk_seed, &path, &pp).unwrap();
+            assert!(sk_mapped.tree.is_mapped());
+            assert_eq!(sk_mapped.to_bytes(), sks[0].to_bytes());
+            sign_and_aggregate(&[sk_mapped], &pks[..1], 3, message.as_ref(), &pp);
+            assert_eq!(
+                SMSigSK::open_tree(&sks[1].sk_seed, &path, &pp).unwrap_err(),
+                Error::KeyMismatch
+            );
+        });
+    }
+
+    #[test]
+    fn test_key_gen_progress() {
+        with_large_stack(|| {
+            let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+            let height = 8;
+            let pp = SMSigParam::setup_with_height(&mut rng, height).unwrap();
+            let reports = std::sync::Mutex::new(Vec::new());
+            let (pk, sk) = SMSigScheme::key_gen_with_progress(&[1u8; 32], &pp, &|p| {
+                reports.lock().unwrap().push(p)
+            });
+            let (expected_pk, expected_sk) = SMSigScheme::key_gen(&[1u8; 32], &pp);
+            assert_eq!(pk, expected_pk);
+            assert_eq!(sk.to_bytes(), expected_sk.to_bytes());
+
+            // all leaves are reported before the nodes
+            let reports = reports.into_inner().unwrap();
+            let leaves = 1 << (height - 1);
+            let split = reports
+                .iter()
+                .take_while(|p| p.stage == Stage::Leaves)
+                .count();
+            let last_done = |reports: &[Progress]| reports.iter().map(|p| p.done).max();
+            assert_eq!(last_done(&reports[..split]), Some(leaves));
+            assert!(reports[split..].iter().all(|p| p.stage == Stage::Nodes));
+            assert_eq!(last_done(&reports[split..]), Some(leaves - 1));
+        });
+    }
+
+    fn fingerprint_from_seed(seed: &[u8], height: usize) -> [u8; 32] {
+        SMSigParam::setup_from_seed(seed, height)
+            .unwrap()
+            .fingerprint()
+    }
+
+    #[test]
+    fn test_setup_from_seed() {
+        with_large_stack(|| {
+            let pp = SMSigParam::setup_from_seed(DEFAULT_SETUP_SEED, HEIGHT).unwrap();
+            assert_param_round_trip(&pp);
+
+            // everyone derives the same parameters; the fingerprint is pinned so
+            // that changes to the derivation are noticed
+            let fingerprint: String = pp
+                .fingerprint()
+                .iter()
+                .map(|b| format!("{:02x}", b))
+                .collect();
+            assert_eq!(
+                fingerprint,
+                "d58f3b759a544fffb21e89b057b389436258fb637626c31375d0ea3f7008a857"
+            );
+            assert_eq!(
+                fingerprint_from_seed(DEFAULT_SETUP_SEED, HEIGHT),
+                pp.fingerprint()
+            );
+            assert_ne!(
+                fingerprint_from_seed(b"another seed", HEIGHT),
+                pp.fingerprint()
+            );
+            assert_ne!(
+                fingerprint_from_seed(DEFAULT_SETUP_SEED, 4),
+                pp.fingerprint()
+            );
+
+            // every entry is derived separately
+            assert_ne!(pp.hvc_hasher.param_h[0], pp.hvc_hasher.param_h[1]);
+            assert_ne!(pp.hvc_hasher.param_h[0], pp.hots_hasher.param_h[0]);
+
+            assert_eq!(
+                SMSigParam::setup_from_seed(DEFAULT_SETUP_SEED, 1).unwrap_err(),
+                Error::InvalidHeight(1)
+            );
+
+            let pp = SMSigParam::setup_from_seed(DEFAULT_SETUP_SEED, 3).unwrap();
+            let (pk, sk) = SMSigScheme::key_gen(&[1u8; 32], &pp);
+            sign_and_aggregate(&[sk], &[pk], 2, b"message", &pp);
+
+            // the hash mode changes neither the keys nor the signatures
+            let fixed_base = pp.clone().with_hash_mode(HashMode::FixedBase);
+            assert_eq!(fixed_base.fingerprint(), pp.fingerprint());
+            let (fixed_pk, fixed_sk) = SMSigScheme::key_gen(&[1u8; 32], &fixed_base);
+            assert_eq!(fixed_pk, pk);
+            sign_and_aggregate(&[fixed_sk], &[pk], 2, b"message", &fixed_base);
+        });
+    }
+
+    #[test]
+    fn test_deserialization_failures() {
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+        let pp = SMSigScheme::setup(&mut rng);
+        let bytes = random_signatures(&mut rng, &pp, 1).0[0].to_bytes();
+
+        // version
+        let mut invalid = bytes.clone();
+        invalid[0] = ENCODING_VERSION + 1;
+        assert_eq!(
+            decoding_error(&invalid),
+            Error::UnsupportedVersion(ENCODING_VERSION + 1)
+        );
+
+        // truncated and trailing bytes
+        assert_eq!(
+            decoding_error(&bytes[..bytes.len() - 1]),
+            Error::UnexpectedEnd
+        );
+        let mut invalid = bytes.clone();
+        invalid.push(0);
+        assert_eq!(decoding_error(&invalid), Error::TrailingBytes(1));
+
+        // index out of range
+        let mut invalid = bytes.clone();
+        invalid[1..9].copy_from_slice(&(1u64 << (HEIGHT - 1)).to_le_bytes());
+        assert_eq!(
+            decoding_error(&invalid),
+            Error::InvalidIndex(1 << (HEIGHT - 1))
+        );
+
+        // path length
+        let mut invalid = bytes.clone();
+        invalid[10..14].copy_from_slice(&(MAX_HEIGHT as u32).to_le_bytes());
+        assert_eq!(
+            decoding_error(&invalid),
+            Error::InvalidPathLength(MAX_HEIGHT)
+        );
+
+        // fresh signatures have binary decompositions
+        let mut invalid = bytes;
+        invalid[14..18].copy_from_slice(&2i32.to_le_bytes());
+        assert_eq!(decoding_error(&invalid), Error::InvalidCoefficient(2));
+    }
+
     #[test]
     fn test_smsig() {
         let message = "this is the message to sign";
@@ -186,8 +1099,8 @@ mod test {
             rng.fill_bytes(&mut seed);
             let (pk, sk) = SMSigScheme::key_gen(&seed, &pp);
             for _ in 0..10 {
-                let index = rng.next_u32() % (1 << HEIGHT - 1);
-                let sig = SMSigScheme::sign(&sk, index as usize, message.as_ref(), &pp);
+                let index = rng.next_u32() % (1 << (HEIGHT - 1));
+                let sig = SMSigScheme::sign(&sk, index as usize, message.as_ref(), &pp).unwrap();
                 assert!(SMSigScheme::verify(&pk, message.as_ref(), &sig, &pp))
             }
         }
@@ -199,19 +1112,19 @@ mod test {
                 rng.fill_bytes(&mut seed);
                 let (pk, sk) = SMSigScheme::key_gen(&seed, &pp);
 
-                let sig = SMSigScheme::sign(&sk, index as usize, message.as_ref(), &pp);
+                let sig = SMSigScheme::sign(&sk, index as usize, message.as_ref(), &pp).unwrap();
                 assert!(SMSigScheme::verify(&pk, message.as_ref(), &sig, &pp));
                 pks.push(pk);
                 sigs.push(sig);
             }
 
-            let agg_sig = SMSigScheme::aggregate(&sigs, &pks);
+            let agg_sig = SMSigScheme::aggregate(&sigs, &pks, message.as_ref()).unwrap();
             assert!(SMSigScheme::batch_verify(
                 &pks,
                 message.as_ref(),
                 &agg_sig,
                 &pp
-            ))
+            ));
         }
     }
 }
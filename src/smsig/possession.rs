@@ -0,0 +1,84 @@
+//! Proofs of possession of secret keys.
+
+use super::{SMSigPK, SMSigParam, SMSigSK, SMSigScheme, SMSignature, SCHEME_ID};
+use crate::{codec::to_bytes, error::Result, MultiSig};
+
+/// A signature of the registration message of a key, with the one-time key
+/// at [`SMSigScheme::possession_index`].
+pub type ProofOfPossession = SMSignature;
+
+/// The message signed by a proof of possession of `pk`.
+fn registration_message(pk: &SMSigPK) -> Vec<u8> {
+    [SCHEME_ID, b" proof of possession".as_ref(), &to_bytes(pk)].concat()
+}
+
+impl SMSigScheme {
+    /// The index of the one-time key reserved for proofs of possession, i.e.,
+    /// the last leaf of the tree. It must not be used to sign anything else;
+    /// [`StatefulSigner`](super::StatefulSigner) and
+    /// [`SMSigLazySK`](super::SMSigLazySK) refuse to.
+    pub fn possession_index(pp: &SMSigParam) -> usize {
+        (1 << (pp.height - 1)) - 1
+    }
+
+    /// Prove the knowledge of `sk` by signing the registration message of its
+    /// public key. The proof is deterministic, so it can be generated again
+    /// without wearing out the reserved one-time key.
+    pub fn prove_possession(sk: &SMSigSK, pp: &SMSigParam) -> Result<ProofOfPossession> {
+        let pk = sk.tree.root();
+        Self::sign(
+            sk,
+            Self::possession_index(pp),
+            &registration_message(&pk),
+            pp,
+        )
+    }
+
+    /// Check a proof of possession of the secret key of `pk`, e.g. once when
+    /// the key is registered.
+    pub fn verify_possession(pk: &SMSigPK, proof: &ProofOfPossession, pp: &SMSigParam) -> bool {
+        !proof.is_aggregated()
+            && proof.index() == Self::possession_index(pp)
+            && Self::verify(pk, &registration_message(pk), proof, pp)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::smsig::test::with_large_stack;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    #[test]
+    fn test_possession() {
+        with_large_stack(|| {
+            let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+            let pp = SMSigParam::setup_with_height(&mut rng, 3).unwrap();
+            assert_eq!(SMSigScheme::possession_index(&pp), 3);
+            let (pk, sk) = SMSigScheme::key_gen(&[1; 32], &pp);
+            let (other_pk, other_sk) = SMSigScheme::key_gen(&[2; 32], &pp);
+
+            let proof = SMSigScheme::prove_possession(&sk, &pp).unwrap();
+            assert!(SMSigScheme::verify_possession(&pk, &proof, &pp));
+            assert_eq!(
+                SMSigScheme::prove_possession(&sk, &pp).unwrap().to_bytes(),
+                proof.to_bytes()
+            );
+
+            // the proof is bound to the key
+            assert!(!SMSigScheme::verify_possession(&other_pk, &proof, &pp));
+            let other_proof = SMSigScheme::prove_possession(&other_sk, &pp).unwrap();
+            assert!(!SMSigScheme::verify_possession(&pk, &other_proof, &pp));
+
+            // a signature of the registration message at another index, or of
+            // another message, is not a proof
+            let message = registration_message(&pk);
+            let sig = SMSigScheme::sign(&sk, 0, &message, &pp).unwrap();
+            assert!(SMSigScheme::verify(&pk, &message, &sig, &pp));
+            assert!(!SMSigScheme::verify_possession(&pk, &sig, &pp));
+            let sig = SMSigScheme::sign(&sk, 3, b"message", &pp).unwrap();
+            assert!(!SMSigScheme::verify_possession(&pk, &sig, &pp));
+        });
+    }
+}
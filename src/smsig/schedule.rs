@@ -0,0 +1,240 @@
+//! The mapping from time slots to one-time key indices.
+
+use super::{SMSigParam, SMSigScheme, SMSignature, StatefulSigner};
+use crate::error::{Error, Result};
+use std::time::{Duration, SystemTime};
+
+/// A source of the current time.
+pub trait Clock {
+    fn now(&self) -> SystemTime;
+}
+
+/// The clock of the system.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// Time slots of a fixed duration from a genesis time. All signers use the
+/// one-time key at the index of the current slot.
+///
+/// There is one slot per leaf of the tree, except for the last one, which is
+/// reserved for the proof of possession.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SlotSchedule {
+    genesis: SystemTime,
+    slot_duration: Duration,
+    num_slots: usize,
+}
+
+impl SlotSchedule {
+    /// The schedule of the trees of `pp` starting at `genesis`.
+    pub fn new(genesis: SystemTime, slot_duration: Duration, pp: &SMSigParam) -> Result<Self> {
+        if slot_duration.is_zero() {
+            return Err(Error::InvalidSlotDuration);
+        }
+        Ok(Self {
+            genesis,
+            slot_duration,
+            num_slots: SMSigScheme::possession_index(pp),
+        })
+    }
+
+    /// The start of the first slot.
+    pub fn genesis(&self) -> SystemTime {
+        self.genesis
+    }
+
+    /// The duration of each slot.
+    pub fn slot_duration(&self) -> Duration {
+        self.slot_duration
+    }
+
+    /// The number of slots; the schedule ends after the last one.
+    pub fn num_slots(&self) -> usize {
+        self.num_slots
+    }
+
+    /// The slot that contains `time`.
+    pub fn slot_at(&self, time: SystemTime) -> Result<usize> {
+        let elapsed = time
+            .duration_since(self.genesis)
+            .map_err(|_| Error::BeforeGenesis)?;
+        let slot = elapsed.as_nanos() / self.slot_duration.as_nanos();
+        match usize::try_from(slot) {
+            Ok(slot) if slot < self.num_slots => Ok(slot),
+            Ok(slot) => Err(Error::InvalidIndex(slot)),
+            Err(_) => Err(Error::InvalidIndex(usize::MAX)),
+        }
+    }
+
+    /// The current slot according to `clock`.
+    pub fn current_slot(&self, clock: &impl Clock) -> Result<usize> {
+        self.slot_at(clock.now())
+    }
+
+    /// The start time of `slot`.
+    pub fn slot_start(&self, slot: usize) -> Result<SystemTime> {
+        if slot >= self.num_slots {
+            return Err(Error::InvalidIndex(slot));
+        }
+        self.slot_duration
+            .checked_mul(slot as u32)
+            .and_then(|offset| self.genesis.checked_add(offset))
+            .ok_or(Error::InvalidIndex(slot))
+    }
+
+    /// Check that `slot` exists and has not ended according to `clock`.
+    pub fn check_slot(&self, clock: &impl Clock, slot: usize) -> Result<()> {
+        if slot >= self.num_slots {
+            return Err(Error::InvalidIndex(slot));
+        }
+        match self.current_slot(clock) {
+            Ok(current) if slot < current => Err(Error::SlotExpired(slot)),
+            Err(Error::InvalidIndex(_)) => Err(Error::SlotExpired(slot)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Sign `message` with the one-time key of the current slot. The signer
+    /// records the slot, so a second message in the same slot is refused with
+    /// [`Error::IndexReused`].
+    pub fn sign(
+        &self,
+        clock: &impl Clock,
+        signer: &mut StatefulSigner,
+        message: &[u8],
+        pp: &SMSigParam,
+    ) -> Result<SMSignature> {
+        signer.sign(self.current_slot(clock)?, message, pp)
+    }
+
+    /// Sign `message` with the one-time key of `slot`, which must not have
+    /// ended yet nor have been signed in before.
+    pub fn sign_slot(
+        &self,
+        clock: &impl Clock,
+        signer: &mut StatefulSigner,
+        slot: usize,
+        message: &[u8],
+        pp: &SMSigParam,
+    ) -> Result<SMSignature> {
+        self.check_slot(clock, slot)?;
+        signer.sign(slot, message, pp)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{smsig::test::with_large_stack, MultiSig};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+    use std::cell::Cell;
+
+    struct TestClock(Cell<SystemTime>);
+
+    impl Clock for TestClock {
+        fn now(&self) -> SystemTime {
+            self.0.get()
+        }
+    }
+
+    impl TestClock {
+        fn advance(&self, duration: Duration) {
+            self.0.set(self.0.get() + duration)
+        }
+    }
+
+    #[test]
+    fn test_slot_schedule() {
+        with_large_stack(|| {
+            let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+            let pp = SMSigParam::setup_with_height(&mut rng, 3).unwrap();
+            let (pk, sk) = SMSigScheme::key_gen(&[1; 32], &pp);
+            let dir = tempfile::tempdir().unwrap();
+            let mut signer = StatefulSigner::create(sk, dir.path().join("state")).unwrap();
+            let genesis = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+            let slot = Duration::from_secs(12);
+            let schedule = SlotSchedule::new(genesis, slot, &pp).unwrap();
+            assert_eq!(schedule.num_slots(), 3);
+            assert_eq!(
+                SlotSchedule::new(genesis, Duration::ZERO, &pp).unwrap_err(),
+                Error::InvalidSlotDuration
+            );
+
+            let clock = TestClock(Cell::new(genesis - Duration::from_secs(1)));
+            assert_eq!(
+                schedule.current_slot(&clock).unwrap_err(),
+                Error::BeforeGenesis
+            );
+            assert!(schedule.check_slot(&clock, 0).is_ok());
+
+            clock.advance(Duration::from_secs(1));
+            assert_eq!(schedule.current_slot(&clock).unwrap(), 0);
+            clock.advance(slot + Duration::from_secs(11));
+            assert_eq!(schedule.current_slot(&clock).unwrap(), 1);
+            assert_eq!(schedule.slot_start(1).unwrap(), genesis + slot);
+
+            // signatures use the index of the current slot
+            let sig = schedule.sign(&clock, &mut signer, b"message", &pp).unwrap();
+            assert_eq!(sig.index(), 1);
+            assert!(SMSigScheme::verify(&pk, b"message", &sig, &pp));
+
+            // a slot is only signed in once
+            assert_eq!(
+                schedule
+                    .sign(&clock, &mut signer, b"other", &pp)
+                    .unwrap_err(),
+                Error::IndexReused(1)
+            );
+            assert_eq!(
+                schedule
+                    .sign_slot(&clock, &mut signer, 1, b"other", &pp)
+                    .unwrap_err(),
+                Error::IndexReused(1)
+            );
+
+            // past slots and slots beyond the tree are refused
+            assert_eq!(
+                schedule.check_slot(&clock, 0).unwrap_err(),
+                Error::SlotExpired(0)
+            );
+            assert_eq!(
+                schedule
+                    .sign_slot(&clock, &mut signer, 0, b"message", &pp)
+                    .unwrap_err(),
+                Error::SlotExpired(0)
+            );
+            assert!(schedule.check_slot(&clock, 1).is_ok());
+            assert!(schedule.check_slot(&clock, 2).is_ok());
+            let sig = schedule
+                .sign_slot(&clock, &mut signer, 2, b"message", &pp)
+                .unwrap();
+            assert_eq!(sig.index(), 2);
+            assert_eq!(
+                schedule.check_slot(&clock, 3).unwrap_err(),
+                Error::InvalidIndex(3)
+            );
+            clock.advance(slot * 2);
+            assert_eq!(
+                schedule.current_slot(&clock).unwrap_err(),
+                Error::InvalidIndex(3)
+            );
+            assert_eq!(
+                schedule
+                    .sign(&clock, &mut signer, b"message", &pp)
+                    .unwrap_err(),
+                Error::InvalidIndex(3)
+            );
+            assert_eq!(
+                schedule.check_slot(&clock, 2).unwrap_err(),
+                Error::SlotExpired(2)
+            );
+        });
+    }
+}
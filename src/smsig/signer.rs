@@ -0,0 +1,339 @@
+use super::{ProofOfPossession, SMSigPK, SMSigParam, SMSigSK, SMSigScheme, SMSignature};
+use crate::{
+    codec::{Decode, Encode, Reader},
+    durable::sync_parent,
+    error::{Error, Result},
+    param::check_height,
+    MultiSig,
+};
+use std::{
+    fs::{self, File, OpenOptions, TryLockError},
+    io::Write,
+    path::{Path, PathBuf},
+    process,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// The set of consumed one-time key indices, bound to the root of the key.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct SignerState {
+    root: SMSigPK,
+    num_indices: usize,
+    used: Vec<u64>,
+}
+
+impl SignerState {
+    pub(crate) fn new(root: SMSigPK, num_indices: usize) -> Self {
+        Self {
+            root,
+            num_indices,
+            used: vec![0; num_indices.div_ceil(64)],
+        }
+    }
+
+    pub(crate) fn is_used(&self, index: usize) -> bool {
+        index < self.num_indices && self.used[index / 64] >> (index % 64) & 1 == 1
+    }
+
+    /// Mark an index as used; fails if it is out of range or already used.
+    pub(crate) fn consume(&mut self, index: usize) -> Result<()> {
+        if index >= self.num_indices {
+            return Err(Error::InvalidIndex(index));
+        }
+        if self.is_used(index) {
+            return Err(Error::IndexReused(index));
+        }
+        self.used[index / 64] |= 1 << (index % 64);
+        Ok(())
+    }
+
+    fn release(&mut self, index: usize) {
+        self.used[index / 64] &= !(1 << (index % 64));
+    }
+
+    pub(crate) fn load(path: &Path) -> Result<Self> {
+        let bytes = fs::read(path)?;
+        crate::codec::from_bytes(&bytes)
+    }
+
+    /// Write the state to a temporary file, sync it, and rename it over `path`,
+    /// so that a crash never leaves a partially written state behind. The
+    /// temporary file has a name of its own, so that writers never share it.
+    pub(crate) fn store(&self, path: &Path) -> Result<()> {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+        let mut tmp_name = path.as_os_str().to_owned();
+        tmp_name.push(format!(
+            ".{}.{}.tmp",
+            process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        let tmp_path = PathBuf::from(tmp_name);
+
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&tmp_path)?;
+        file.write_all(&crate::codec::to_bytes(self))?;
+        file.sync_all()?;
+        fs::rename(&tmp_path, path)?;
+        sync_parent(path)
+    }
+
+    /// Write the state to a new file at `path`; fails if the file exists, even
+    /// if another process creates it concurrently.
+    pub(crate) fn store_new(&self, path: &Path) -> Result<()> {
+        let mut file = OpenOptions::new().write(true).create_new(true).open(path)?;
+        file.write_all(&crate::codec::to_bytes(self))?;
+        file.sync_all()?;
+        sync_parent(path)
+    }
+}
+
+/// Take an exclusive lock on the state at `path`, held until the returned file
+/// is dropped. The lock is on `<path>.lock` rather than on the state, whose
+/// file is replaced at each update; it is released when the process exits, so
+/// a crash does not leave the state locked.
+fn lock_state(path: &Path) -> Result<File> {
+    let mut lock_name = path.as_os_str().to_owned();
+    lock_name.push(".lock");
+    let file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(PathBuf::from(lock_name))?;
+    match file.try_lock() {
+        Ok(()) => Ok(file),
+        Err(TryLockError::WouldBlock) => Err(Error::StateLocked),
+        Err(TryLockError::Error(e)) => Err(e.into()),
+    }
+}
+
+impl Encode for SignerState {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.root.encode(out);
+        out.extend_from_slice(&(self.num_indices as u64).to_le_bytes());
+        self.used
+            .iter()
+            .for_each(|x| out.extend_from_slice(&x.to_le_bytes()));
+    }
+}
+
+impl Decode for SignerState {
+    fn decode_into(&mut self, reader: &mut Reader) -> Result<()> {
+        self.root.decode_into(reader)?;
+        self.num_indices = reader.read_u64()? as usize;
+        if !self.num_indices.is_power_of_two()
+            || check_height(self.num_indices.trailing_zeros() as usize + 1).is_err()
+        {
+            return Err(Error::InvalidTreeSize(self.num_indices));
+        }
+        self.used = (0..self.num_indices.div_ceil(64))
+            .map(|_| reader.read_u64())
+            .collect::<Result<_>>()?;
+        Ok(())
+    }
+}
+
+/// A signer that never signs twice with the same one-time key.
+///
+/// The used indices are stored in a state file, which is updated atomically
+/// before any signature is released. The state is locked for the lifetime of
+/// the signer, so that no other signer, in this or another process, can use
+/// it at the same time.
+#[derive(Debug)]
+pub struct StatefulSigner {
+    sk: SMSigSK,
+    state: SignerState,
+    state_path: PathBuf,
+    _lock: File,
+}
+
+impl StatefulSigner {
+    /// Create a signer with a fresh state file at `state_path`.
+    /// Fails if the file already exists, since overwriting it would allow
+    /// reusing indices.
+    pub fn create(sk: SMSigSK, state_path: impl AsRef<Path>) -> Result<Self> {
+        let state_path = state_path.as_ref().to_path_buf();
+        let state = SignerState::new(sk.tree.root(), sk.tree.num_leaves());
+        state.store_new(&state_path)?;
+        let lock = lock_state(&state_path)?;
+        Ok(Self {
+            sk,
+            state,
+            state_path,
+            _lock: lock,
+        })
+    }
+
+    /// Resume a signer from an existing state file. Fails with
+    /// [`Error::StateLocked`] while another signer uses the state.
+    pub fn open(sk: SMSigSK, state_path: impl AsRef<Path>) -> Result<Self> {
+        let state_path = state_path.as_ref().to_path_buf();
+        // the state is only read once it is locked
+        let lock = lock_state(&state_path)?;
+        let state = SignerState::load(&state_path)?;
+        if state.root != sk.tree.root() {
+            return Err(Error::KeyMismatch);
+        }
+        Ok(Self {
+            sk,
+            state,
+            state_path,
+            _lock: lock,
+        })
+    }
+
+    /// The public key of the signer.
+    pub fn pk(&self) -> SMSigPK {
+        self.sk.tree.root()
+    }
+
+    /// Whether the one-time key at `index` has been used.
+    pub fn is_used(&self, index: usize) -> bool {
+        self.state.is_used(index)
+    }
+
+    /// Sign a message with the one-time key at `index`.
+    /// The index is marked as used and persisted before the signature is returned;
+    /// if persisting fails, no signature is produced. The index of
+    /// [`SMSigScheme::possession_index`] is rejected.
+    pub fn sign(&mut self, index: usize, message: &[u8], pp: &SMSigParam) -> Result<SMSignature> {
+        if index == SMSigScheme::possession_index(pp) {
+            return Err(Error::ReservedIndex(index));
+        }
+        self.state.consume(index)?;
+        if let Err(e) = self.state.store(&self.state_path) {
+            self.state.release(index);
+            return Err(e);
+        }
+        SMSigScheme::sign(&self.sk, index, message, pp)
+    }
+
+    /// Prove the possession of the secret key, see
+    /// [`SMSigScheme::prove_possession`]. This does not change the state.
+    pub fn prove_possession(&self, pp: &SMSigParam) -> Result<ProofOfPossession> {
+        SMSigScheme::prove_possession(&self.sk, pp)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    // signatures are kept off the test's stack frame
+    fn sign_and_verify(signer: &mut StatefulSigner, index: usize, pp: &SMSigParam) {
+        let sig = signer.sign(index, b"message", pp).unwrap();
+        assert!(SMSigScheme::verify(&signer.pk(), b"message", &sig, pp));
+    }
+
+    fn sign_error(signer: &mut StatefulSigner, index: usize, pp: &SMSigParam) -> Error {
+        signer.sign(index, b"another message", pp).unwrap_err()
+    }
+
+    fn prove_and_verify(signer: &StatefulSigner, pp: &SMSigParam) {
+        let proof = signer.prove_possession(pp).unwrap();
+        assert!(SMSigScheme::verify_possession(&signer.pk(), &proof, pp));
+    }
+
+    #[test]
+    fn test_stateful_signer() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state");
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+        let pp = SMSigParam::setup_with_height(&mut rng, 3).unwrap();
+        let (pk, sk) = SMSigScheme::key_gen(&[1u8; 32], &pp);
+
+        let mut signer = StatefulSigner::create(sk.clone(), &path).unwrap();
+        assert_eq!(signer.pk(), pk);
+        sign_and_verify(&mut signer, 1, &pp);
+        assert_eq!(sign_error(&mut signer, 1, &pp), Error::IndexReused(1));
+        assert_eq!(sign_error(&mut signer, 4, &pp), Error::InvalidIndex(4));
+        assert_eq!(sign_error(&mut signer, 3, &pp), Error::ReservedIndex(3));
+        prove_and_verify(&signer, &pp);
+        assert!(!signer.is_used(3));
+        assert_eq!(
+            StatefulSigner::create(sk.clone(), &path).unwrap_err(),
+            Error::Io(std::io::ErrorKind::AlreadyExists)
+        );
+
+        // of concurrent creations, exactly one succeeds
+        let other_path = dir.path().join("other");
+        let created = std::thread::scope(|s| {
+            let handles: Vec<_> = (0..4)
+                .map(|_| s.spawn(|| StatefulSigner::create(sk.clone(), &other_path).is_ok()))
+                .collect();
+            handles
+                .into_iter()
+                .map(|h| h.join().unwrap())
+                .filter(|&ok| ok)
+                .count()
+        });
+        assert_eq!(created, 1);
+
+        // the state is locked while the signer is alive
+        assert_eq!(
+            StatefulSigner::open(sk.clone(), &path).unwrap_err(),
+            Error::StateLocked
+        );
+
+        // the state survives a restart
+        drop(signer);
+        let mut signer = StatefulSigner::open(sk.clone(), &path).unwrap();
+        assert!(signer.is_used(1));
+        assert_eq!(sign_error(&mut signer, 1, &pp), Error::IndexReused(1));
+        sign_and_verify(&mut signer, 2, &pp);
+
+        // the state is bound to the key
+        let (_, other_sk) = SMSigScheme::key_gen(&[2u8; 32], &pp);
+        drop(signer);
+        assert_eq!(
+            StatefulSigner::open(other_sk, &path).unwrap_err(),
+            Error::KeyMismatch
+        );
+    }
+
+    #[test]
+    fn test_signer_state() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state");
+
+        let num_indices = 1 << 10;
+        let mut state = SignerState::new(SMSigPK::default(), num_indices);
+        assert!(!state.is_used(5));
+        state.consume(5).unwrap();
+        assert!(state.is_used(5));
+        assert_eq!(state.consume(5), Err(Error::IndexReused(5)));
+        assert_eq!(
+            state.consume(num_indices),
+            Err(Error::InvalidIndex(num_indices))
+        );
+        assert!(!state.is_used(num_indices));
+
+        state.store(&path).unwrap();
+        // no temporary file is left behind
+        let names: Vec<_> = fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        assert_eq!(names, ["state"]);
+        let mut loaded = SignerState::load(&path).unwrap();
+        assert_eq!(loaded, state);
+        assert_eq!(loaded.consume(5), Err(Error::IndexReused(5)));
+        loaded.consume(num_indices - 1).unwrap();
+
+        // overwritten atomically
+        loaded.store(&path).unwrap();
+        assert!(SignerState::load(&path).unwrap().is_used(num_indices - 1));
+
+        // corrupted state files are rejected
+        let bytes = fs::read(&path).unwrap();
+        fs::write(&path, &bytes[..bytes.len() - 1]).unwrap();
+        assert_eq!(SignerState::load(&path), Err(Error::UnexpectedEnd));
+        assert_eq!(
+            SignerState::load(&dir.path().join("missing")),
+            Err(Error::Io(std::io::ErrorKind::NotFound))
+        );
+    }
+}
@@ -0,0 +1,428 @@
+//! Aggregated signatures of a subset of a fixed committee.
+
+use super::{Aggregator, SMSigPK, SMSigParam, SMSigScheme, SMSignature, SCHEME_ID};
+use crate::{
+    codec::{impl_serialization, Decode, Encode, Reader, ENCODING_VERSION},
+    error::{Error, Result},
+    randomizer::Randomizers,
+};
+
+/// The positions of the committee members that signed.
+///
+/// Encoded as the committee size in a u32, followed by one bit per member,
+/// least significant bit first, with zero padding bits.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SignerBitmap {
+    len: usize,
+    bits: Vec<u8>,
+}
+
+impl SignerBitmap {
+    /// An empty bitmap for a committee of `len` members.
+    pub fn new(len: usize) -> Self {
+        Self {
+            len,
+            bits: vec![0; len.div_ceil(8)],
+        }
+    }
+
+    /// The bitmap of the given positions.
+    pub fn from_signers(len: usize, signers: &[usize]) -> Result<Self> {
+        let mut res = Self::new(len);
+        for &signer in signers {
+            res.insert(signer)?;
+        }
+        Ok(res)
+    }
+
+    /// Add the member at position `signer`.
+    pub fn insert(&mut self, signer: usize) -> Result<()> {
+        if signer >= self.len {
+            return Err(Error::UnknownSigner(signer));
+        }
+        if self.contains(signer) {
+            return Err(Error::DuplicateSigner(signer));
+        }
+        self.bits[signer >> 3] |= 1 << (signer & 7);
+        Ok(())
+    }
+
+    /// Whether the member at position `signer` is set.
+    pub fn contains(&self, signer: usize) -> bool {
+        signer < self.len && (self.bits[signer >> 3] >> (signer & 7)) & 1 == 1
+    }
+
+    /// The size of the committee.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the committee is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The number of members set.
+    pub fn count(&self) -> usize {
+        self.bits.iter().map(|b| b.count_ones() as usize).sum()
+    }
+
+    /// The positions of the members set, in increasing order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.len).filter(|&i| self.contains(i))
+    }
+
+    /// The union with a bitmap of the same committee and no member in common.
+    pub fn union(&self, other: &Self) -> Result<Self> {
+        if self.len != other.len {
+            return Err(Error::LengthMismatch(self.len, other.len));
+        }
+        if let Some(signer) = self.iter().find(|&i| other.contains(i)) {
+            return Err(Error::DuplicateSigner(signer));
+        }
+        let mut res = self.clone();
+        res.bits
+            .iter_mut()
+            .zip(other.bits.iter())
+            .for_each(|(x, y)| *x |= y);
+        Ok(res)
+    }
+}
+
+impl Encode for SignerBitmap {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&(self.len as u32).to_le_bytes());
+        out.extend_from_slice(&self.bits);
+    }
+}
+
+impl Decode for SignerBitmap {
+    fn decode_into(&mut self, reader: &mut Reader) -> Result<()> {
+        self.len = reader.read_u32()? as usize;
+        self.bits = reader.read_bytes(self.len.div_ceil(8))?.to_vec();
+        if self.len & 7 != 0 && self.bits[self.len >> 3] >> (self.len & 7) != 0 {
+            return Err(Error::InvalidPadding);
+        }
+        Ok(())
+    }
+}
+
+impl_serialization!(SignerBitmap);
+
+/// An aggregated signature together with the committee members it covers.
+///
+/// The randomizers are those of [`Randomizers::from_committee`], so that the
+/// verifier only needs the registered committee and the bitmap.
+#[derive(Debug, Clone, Default)]
+pub struct SubsetSignature {
+    signers: SignerBitmap,
+    signature: SMSignature,
+}
+
+impl SubsetSignature {
+    /// Aggregate the signatures of `message` by the members in `signers`; the
+    /// signatures are given in increasing order of position.
+    pub fn aggregate(
+        committee: &[SMSigPK],
+        signers: &SignerBitmap,
+        sigs: &[SMSignature],
+        message: &[u8],
+    ) -> Result<Self> {
+        if signers.len() != committee.len() {
+            return Err(Error::LengthMismatch(committee.len(), signers.len()));
+        }
+        if sigs.len() != signers.count() {
+            return Err(Error::LengthMismatch(signers.count(), sigs.len()));
+        }
+        let index = sigs.first().ok_or(Error::EmptyInput)?.index();
+        let mut aggregator = Aggregator::new(committee, message, index);
+        for (signer, sig) in signers.iter().zip(sigs) {
+            aggregator.add(signer, sig)?;
+        }
+        aggregator.finalize_subset()
+    }
+
+    pub(super) fn new(signers: SignerBitmap, signature: SMSignature) -> Self {
+        Self { signers, signature }
+    }
+
+    /// Merge with the aggregate of a disjoint set of signers of the same
+    /// committee, e.g. from another aggregator node.
+    ///
+    /// Each member keeps its randomizer of [`Randomizers::from_committee`], so
+    /// the result is the aggregate of the union of the signers, whatever the
+    /// order and the depth of the merges. Its norms, and the bounds it is
+    /// verified against, only depend on the total number of signers.
+    pub fn merge(&self, other: &Self) -> Result<Self> {
+        let signers = self.signers.union(&other.signers)?;
+        let mut signature = self.signature.clone();
+        signature.merge(&other.signature)?;
+        Ok(Self { signers, signature })
+    }
+
+    /// The committee members covered by the signature.
+    pub fn signers(&self) -> &SignerBitmap {
+        &self.signers
+    }
+
+    /// The aggregated signature.
+    pub fn signature(&self) -> &SMSignature {
+        &self.signature
+    }
+
+    /// Verify the signature of `message` against the full committee, in the
+    /// order it was registered in.
+    pub fn verify(&self, committee: &[SMSigPK], message: &[u8], pp: &SMSigParam) -> bool {
+        if self.signers.len() != committee.len()
+            || self.signers.count() == 0
+            || !self.signature.is_aggregated()
+        {
+            return false;
+        }
+        let pks: Vec<SMSigPK> = self.signers.iter().map(|i| committee[i]).collect();
+        let randomizers = Randomizers::from_committee(
+            SCHEME_ID,
+            committee,
+            self.signers.iter(),
+            message,
+            self.signature.index(),
+        );
+        SMSigScheme::batch_verify_with_randomizers(&pks, message, &self.signature, &randomizers, pp)
+    }
+
+    /// The encoded bitmap followed by the compact encoding of the signature.
+    pub fn to_compact_bytes(&self) -> Result<Vec<u8>> {
+        let mut res = self.signers.to_bytes();
+        res.extend(self.signature.to_compact_bytes(self.signers.count())?);
+        Ok(res)
+    }
+
+    /// Decode the output of [`SubsetSignature::to_compact_bytes`].
+    pub fn from_compact_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut reader = Reader::new(bytes);
+        let version = reader.read_u8()?;
+        if version != ENCODING_VERSION {
+            return Err(Error::UnsupportedVersion(version));
+        }
+        let mut signers = SignerBitmap::default();
+        signers.decode_into(&mut reader)?;
+        let num_signers = signers.count();
+        if num_signers == 0 {
+            return Err(Error::EmptyInput);
+        }
+        let rest = reader.read_bytes(reader.remaining())?;
+        let signature = SMSignature::from_compact_bytes(rest, num_signers)?;
+        if !signature.is_aggregated() {
+            return Err(Error::NotRandomized);
+        }
+        Ok(Self { signers, signature })
+    }
+}
+
+impl Encode for SubsetSignature {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.signers.encode(out);
+        self.signature.encode(out);
+    }
+}
+
+impl Decode for SubsetSignature {
+    fn decode_into(&mut self, reader: &mut Reader) -> Result<()> {
+        self.signers.decode_into(reader)?;
+        self.signature.decode_into(reader)?;
+        if !self.signature.is_aggregated() {
+            return Err(Error::NotRandomized);
+        }
+        Ok(())
+    }
+}
+
+impl_serialization!(SubsetSignature);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{smsig::test::with_large_stack, MultiSig};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    const MESSAGE: &[u8] = b"message";
+
+    #[test]
+    fn test_signer_bitmap() {
+        let mut bitmap = SignerBitmap::from_signers(11, &[0, 3, 10]).unwrap();
+        assert_eq!(bitmap.iter().collect::<Vec<_>>(), vec![0, 3, 10]);
+        assert_eq!(bitmap.count(), 3);
+        assert!(!bitmap.contains(11));
+        assert_eq!(bitmap.insert(3).unwrap_err(), Error::DuplicateSigner(3));
+        assert_eq!(bitmap.insert(11).unwrap_err(), Error::UnknownSigner(11));
+
+        let bytes = bitmap.to_bytes();
+        assert_eq!(bytes, [ENCODING_VERSION, 11, 0, 0, 0, 0b1001, 0b100]);
+        assert_eq!(SignerBitmap::from_bytes(&bytes).unwrap(), bitmap);
+        let mut invalid = bytes.clone();
+        invalid[6] |= 0x08;
+        assert_eq!(
+            SignerBitmap::from_bytes(&invalid).unwrap_err(),
+            Error::InvalidPadding
+        );
+        assert_eq!(
+            SignerBitmap::from_bytes(&bytes[..6]).unwrap_err(),
+            Error::UnexpectedEnd
+        );
+
+        let other = SignerBitmap::from_signers(11, &[1, 9]).unwrap();
+        let union = bitmap.union(&other).unwrap();
+        assert_eq!(union.iter().collect::<Vec<_>>(), vec![0, 1, 3, 9, 10]);
+        assert_eq!(union.union(&other).unwrap_err(), Error::DuplicateSigner(1));
+        assert_eq!(
+            bitmap.union(&SignerBitmap::new(12)).unwrap_err(),
+            Error::LengthMismatch(11, 12)
+        );
+    }
+
+    #[test]
+    fn test_subset_signature() {
+        with_large_stack(|| {
+            let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+            let pp = SMSigParam::setup_with_height(&mut rng, 3).unwrap();
+            let index = 1;
+            let (committee, sigs): (Vec<SMSigPK>, Vec<SMSignature>) = (0..5u8)
+                .map(|i| {
+                    let (pk, sk) = SMSigScheme::key_gen(&[i; 32], &pp);
+                    (pk, SMSigScheme::sign(&sk, index, MESSAGE, &pp).unwrap())
+                })
+                .unzip();
+
+            let signers = SignerBitmap::from_signers(5, &[1, 2, 4]).unwrap();
+            let signed: Vec<SMSignature> = signers.iter().map(|i| sigs[i].clone()).collect();
+            let sig = SubsetSignature::aggregate(&committee, &signers, &signed, MESSAGE).unwrap();
+            assert!(sig.verify(&committee, MESSAGE, &pp));
+            assert!(!sig.verify(&committee, b"another message", &pp));
+            // the committee order matters
+            let mut reordered = committee.clone();
+            reordered.swap(0, 1);
+            assert!(!sig.verify(&reordered, MESSAGE, &pp));
+            assert!(!sig.verify(&committee[..4], MESSAGE, &pp));
+
+            // a different bitmap does not verify
+            let mut other = sig.clone();
+            other.signers = SignerBitmap::from_signers(5, &[0, 2, 4]).unwrap();
+            assert!(!other.verify(&committee, MESSAGE, &pp));
+
+            // same result as the streaming aggregator
+            let mut aggregator = Aggregator::new(&committee, MESSAGE, index);
+            for i in [4, 1, 2] {
+                aggregator.add(i, &sigs[i]).unwrap();
+            }
+            let streamed = aggregator.finalize_subset().unwrap();
+            assert_eq!(streamed.to_bytes(), sig.to_bytes());
+
+            // the full committee gives the aggregate of `SMSigScheme`
+            let all = SignerBitmap::from_signers(5, &[0, 1, 2, 3, 4]).unwrap();
+            let full = SubsetSignature::aggregate(&committee, &all, &sigs, MESSAGE).unwrap();
+            assert!(full.verify(&committee, MESSAGE, &pp));
+            assert!(SMSigScheme::batch_verify(
+                &committee,
+                MESSAGE,
+                full.signature(),
+                &pp
+            ));
+
+            // serialization
+            let rec = SubsetSignature::from_bytes(&sig.to_bytes()).unwrap();
+            assert!(rec.verify(&committee, MESSAGE, &pp));
+            let compact = sig.to_compact_bytes().unwrap();
+            assert!(compact.len() < sig.to_bytes().len());
+            let rec = SubsetSignature::from_compact_bytes(&compact).unwrap();
+            assert_eq!(rec.to_bytes(), sig.to_bytes());
+            let unsigned = SubsetSignature::new(signers.clone(), sigs[1].clone());
+            assert_eq!(
+                SubsetSignature::from_bytes(&unsigned.to_bytes()).unwrap_err(),
+                Error::NotRandomized
+            );
+
+            // errors
+            assert_eq!(
+                SubsetSignature::aggregate(&committee, &signers, &signed[..2], MESSAGE)
+                    .unwrap_err(),
+                Error::LengthMismatch(3, 2)
+            );
+            assert_eq!(
+                SubsetSignature::aggregate(&committee[..4], &signers, &signed, MESSAGE)
+                    .unwrap_err(),
+                Error::LengthMismatch(4, 5)
+            );
+            assert_eq!(
+                SubsetSignature::aggregate(&committee, &SignerBitmap::new(5), &[], MESSAGE)
+                    .unwrap_err(),
+                Error::EmptyInput
+            );
+        });
+    }
+
+    #[test]
+    fn test_merge() {
+        with_large_stack(|| {
+            let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+            let pp = SMSigParam::setup_with_height(&mut rng, 3).unwrap();
+            let index = 2;
+            let keys: Vec<_> = (0..6u8)
+                .map(|i| SMSigScheme::key_gen(&[i; 32], &pp))
+                .collect();
+            let committee: Vec<SMSigPK> = keys.iter().map(|(pk, _)| *pk).collect();
+            let sigs: Vec<SMSignature> = keys
+                .iter()
+                .map(|(_, sk)| SMSigScheme::sign(sk, index, MESSAGE, &pp).unwrap())
+                .collect();
+            let aggregate = |signers: &[usize]| {
+                let bitmap = SignerBitmap::from_signers(6, signers).unwrap();
+                let signed: Vec<SMSignature> = signers.iter().map(|&i| sigs[i].clone()).collect();
+                SubsetSignature::aggregate(&committee, &bitmap, &signed, MESSAGE).unwrap()
+            };
+
+            // a tree of aggregators, merged in any order and at any depth
+            let left = aggregate(&[0, 2]);
+            let right = aggregate(&[1, 4]);
+            let leaf = aggregate(&[5]);
+            let flat = aggregate(&[0, 1, 2, 4, 5]);
+            let merged = left.merge(&right).unwrap().merge(&leaf).unwrap();
+            assert!(merged.verify(&committee, MESSAGE, &pp));
+            assert_eq!(merged.to_bytes(), flat.to_bytes());
+            let merged = leaf.merge(&right.merge(&left).unwrap()).unwrap();
+            assert_eq!(merged.to_bytes(), flat.to_bytes());
+
+            // an aggregator node adds its own signatures and the ones of its children
+            let mut aggregator = Aggregator::new(&committee, MESSAGE, index);
+            aggregator.merge(&left).unwrap();
+            aggregator.add(3, &sigs[3]).unwrap();
+            aggregator.merge(&right).unwrap();
+            assert_eq!(aggregator.signers(), vec![0, 1, 2, 3, 4]);
+            let sig = aggregator.finalize_subset().unwrap();
+            assert!(sig.verify(&committee, MESSAGE, &pp));
+            assert_eq!(sig.to_bytes(), aggregate(&[0, 1, 2, 3, 4]).to_bytes());
+
+            // errors
+            assert_eq!(merged.merge(&left).unwrap_err(), Error::DuplicateSigner(0));
+            assert_eq!(
+                aggregator.merge(&leaf.merge(&right).unwrap()).unwrap_err(),
+                Error::DuplicateSigner(1)
+            );
+            let (_, sk) = SMSigScheme::key_gen(&[3; 32], &pp);
+            let bitmap = SignerBitmap::from_signers(6, &[3]).unwrap();
+            let other_index = SubsetSignature::aggregate(
+                &committee,
+                &bitmap,
+                &[SMSigScheme::sign(&sk, 0, MESSAGE, &pp).unwrap()],
+                MESSAGE,
+            )
+            .unwrap();
+            assert_eq!(
+                left.merge(&other_index).unwrap_err(),
+                Error::MixedIndices(index, 0)
+            );
+            let fresh = SubsetSignature::new(bitmap, sigs[3].clone());
+            assert_eq!(left.merge(&fresh).unwrap_err(), Error::NotRandomized);
+        });
+    }
+}
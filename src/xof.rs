@@ -0,0 +1,78 @@
+//! Deterministic expansion of public seeds into parameters.
+
+use rand::{CryptoRng, RngCore};
+use sha3::{
+    digest::{ExtendableOutput, Update, XofReader},
+    Shake256, Shake256Reader,
+};
+
+const DOMAIN: &[u8] = b"squirrel setup v1";
+
+/// A random number generator reading SHAKE256 output, so that anyone holding the
+/// seed can recompute the values drawn from it.
+///
+/// The input is the domain, the seed and a label naming the parameter, each
+/// prefixed with its length, followed by the index of the entry; every entry of
+/// every parameter thus comes from its own XOF call.
+pub(crate) struct XofRng {
+    reader: Shake256Reader,
+}
+
+impl XofRng {
+    pub(crate) fn new(seed: &[u8], label: &[u8], index: usize) -> Self {
+        let mut hasher = Shake256::default();
+        for input in [DOMAIN, seed, label] {
+            hasher.update(&(input.len() as u64).to_le_bytes());
+            hasher.update(input);
+        }
+        hasher.update(&(index as u64).to_le_bytes());
+        Self {
+            reader: hasher.finalize_xof(),
+        }
+    }
+}
+
+impl RngCore for XofRng {
+    fn next_u32(&mut self) -> u32 {
+        let mut bytes = [0u8; 4];
+        self.reader.read(&mut bytes);
+        u32::from_le_bytes(bytes)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut bytes = [0u8; 8];
+        self.reader.read(&mut bytes);
+        u64::from_le_bytes(bytes)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.reader.read(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl CryptoRng for XofRng {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn output(seed: &[u8], label: &[u8], index: usize) -> u64 {
+        XofRng::new(seed, label, index).next_u64()
+    }
+
+    #[test]
+    fn test_domain_separation() {
+        let reference = output(b"seed", b"label", 0);
+        assert_eq!(output(b"seed", b"label", 0), reference);
+        assert_ne!(output(b"seed", b"label", 1), reference);
+        assert_ne!(output(b"seed", b"other", 0), reference);
+        assert_ne!(output(b"other", b"label", 0), reference);
+        // inputs are length prefixed, so they cannot be shifted into each other
+        assert_ne!(output(b"seedl", b"abel", 0), reference);
+    }
+}